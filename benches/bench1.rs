@@ -634,6 +634,38 @@ fn multi_cartesian_product_iterator(c: &mut Criterion) {
     });
 }
 
+fn multi_cartesian_product_map_vec(c: &mut Criterion) {
+    let xs = [vec![0; 16], vec![0; 16], vec![0; 16]];
+
+    c.bench_function("multi cartesian product map vec", move |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            for x in xs
+                .iter()
+                .multi_cartesian_product()
+                .map(|p| p.iter().map(|x| *x + 1).collect_vec())
+            {
+                sum += x[0];
+            }
+            sum
+        })
+    });
+}
+
+fn multi_cartesian_product_fill(c: &mut Criterion) {
+    let xs = [vec![0; 16], vec![0; 16], vec![0; 16]];
+
+    c.bench_function("multi cartesian product fill", move |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            xs.iter()
+                .multi_cartesian_product_fill(|p, buf| buf.extend(p.iter().map(|x| *x + 1)))
+                .for_each_filled(|x| sum += x[0]);
+            sum
+        })
+    });
+}
+
 fn cartesian_product_nested_for(c: &mut Criterion) {
     let xs = vec![0; 16];
 
@@ -756,6 +788,8 @@ criterion_group!(
     vec_iter_mut_partition,
     cartesian_product_iterator,
     multi_cartesian_product_iterator,
+    multi_cartesian_product_map_vec,
+    multi_cartesian_product_fill,
     cartesian_product_nested_for,
     all_equal,
     all_equal_for,
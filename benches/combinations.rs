@@ -90,6 +90,18 @@ fn comb_c3(c: &mut Criterion) {
     });
 }
 
+fn comb_c3_next_into(c: &mut Criterion) {
+    c.bench_function("comb c3 next_into", move |b| {
+        b.iter(|| {
+            let mut it = (0..N3).combinations(3);
+            let mut buf = Vec::with_capacity(3);
+            while it.next_into(&mut buf) {
+                black_box(&buf);
+            }
+        })
+    });
+}
+
 fn comb_c4(c: &mut Criterion) {
     c.bench_function("comb c4", move |b| {
         b.iter(|| {
@@ -110,8 +122,31 @@ fn comb_c14(c: &mut Criterion) {
     });
 }
 
+fn comb_max_by_key(c: &mut Criterion) {
+    c.bench_function("comb max_by_key", move |b| {
+        b.iter(|| (0..N3).combinations(3).max_by_key(|c| c[0] ^ c[1] ^ c[2]))
+    });
+}
+
+fn comb_max_by_key_slice(c: &mut Criterion) {
+    c.bench_function("comb max_by_key slice", move |b| {
+        b.iter(|| (0..N3).combinations_max_by_key(3, |c| c[0] ^ c[1] ^ c[2]))
+    });
+}
+
 criterion_group!(
-    benches, comb_for1, comb_for2, comb_for3, comb_for4, comb_c1, comb_c2, comb_c3, comb_c4,
+    benches,
+    comb_for1,
+    comb_for2,
+    comb_for3,
+    comb_for4,
+    comb_c1,
+    comb_c2,
+    comb_c3,
+    comb_c3_next_into,
+    comb_c4,
     comb_c14,
+    comb_max_by_key,
+    comb_max_by_key_slice,
 );
 criterion_main!(benches);
@@ -417,6 +417,18 @@ bench_specializations! {
         }
         v.iter().combinations(4)
     }
+    combinations_copied3 {
+        {
+            let v = black_box(vec![0_i32; 23]);
+        }
+        v.iter().copied().combinations_copied(3)
+    }
+    combinations_i32_3 {
+        {
+            let v = black_box(vec![0_i32; 23]);
+        }
+        v.iter().copied().combinations(3)
+    }
     combinations_with_replacement1 {
         {
             let v = black_box(vec![0; 4096]);
@@ -72,7 +72,7 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        size_hint::add(self.i.size_hint(), self.j.size_hint())
+        size_hint::saturating_add(self.i.size_hint(), self.j.size_hint())
     }
 
     fn fold<B, F>(self, mut init: B, mut f: F) -> B
@@ -424,9 +424,9 @@ where
     fn size_hint(&self) -> (usize, Option<usize>) {
         // Not ExactSizeIterator because size may be larger than usize
         // Compute a * b_orig + b for both lower and upper bound
-        let mut sh = size_hint::mul(self.a.size_hint(), self.b_orig.size_hint());
+        let mut sh = size_hint::saturating_mul(self.a.size_hint(), self.b_orig.size_hint());
         if matches!(self.a_cur, Some(Some(_))) {
-            sh = size_hint::add(sh, self.b.size_hint());
+            sh = size_hint::saturating_add(sh, self.b.size_hint());
         }
         sh
     }
@@ -760,7 +760,7 @@ macro_rules! impl_tuple_combination {
                 let (mut n_min, mut n_max) = self.iter.size_hint();
                 n_min = checked_binomial(n_min, K).unwrap_or(usize::MAX);
                 n_max = n_max.and_then(|n| checked_binomial(n, K));
-                size_hint::add(self.c.size_hint(), (n_min, n_max))
+                size_hint::saturating_add(self.c.size_hint(), (n_min, n_max))
             }
 
             fn count(self) -> usize {
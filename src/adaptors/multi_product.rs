@@ -2,6 +2,7 @@
 use Option::{self as State, None as ProductEnded, Some as ProductInProgress};
 use Option::{self as CurrentItems, None as NotYetPopulated, Some as Populated};
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 
 use crate::size_hint;
@@ -46,7 +47,44 @@ where
     I: Iterator + Clone + std::fmt::Debug,
     I::Item: Clone + std::fmt::Debug,
 {
-    debug_fmt_fields!(MultiProductInner, iters, cur);
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Sub-iterators can be huge, only summarize the state of each axis.
+        let axes = self.iters.iter().enumerate().map(|(i, iter)| AxisSummary {
+            current: self.cur.as_ref().map(|values| &values[i]),
+            remaining: iter.remaining_hint(),
+            len: iter.len_hint(),
+        });
+        f.debug_struct("MultiProductInner")
+            .field("axes", &axes.collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Summary of the state of an axis of a `MultiProduct`, only used for `Debug`.
+struct AxisSummary<'a, T> {
+    current: Option<&'a T>,
+    remaining: size_hint::SizeHint,
+    len: size_hint::SizeHint,
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for AxisSummary<'_, T> {
+    debug_fmt_fields!(AxisSummary, current, remaining, len);
+}
+
+/// The current product of a `MultiProduct`, displayed as a tuple.
+struct CurrentTuple<'a, T>(&'a [T]);
+
+impl<T: std::fmt::Display> std::fmt::Display for CurrentTuple<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("(")?;
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            value.fmt(f)?;
+        }
+        f.write_str(")")
+    }
 }
 
 /// Create a new cartesian product iterator over an arbitrary number
@@ -69,6 +107,135 @@ where
     MultiProduct(ProductInProgress(inner))
 }
 
+/// Create a new cartesian product iterator over an arbitrary number
+/// of iterators of the same type, with room for `axes` iterators.
+///
+/// Iterator element is of type `Vec<H::Item::Item>`.
+pub fn multi_cartesian_product_with_axes<H>(
+    iters: H,
+    axes: usize,
+) -> MultiProduct<<H::Item as IntoIterator>::IntoIter>
+where
+    H: Iterator,
+    H::Item: IntoIterator,
+    <H::Item as IntoIterator>::IntoIter: Clone,
+    <H::Item as IntoIterator>::Item: Clone,
+{
+    let mut axes = Vec::with_capacity(axes);
+    axes.extend(iters.map(|i| MultiProductIter::new(i.into_iter())));
+    let inner = MultiProductInner {
+        iters: axes,
+        cur: NotYetPopulated,
+    };
+    MultiProduct(ProductInProgress(inner))
+}
+
+/// Builds a [`MultiProduct`] over axes of different iterator types yielding the same items.
+///
+/// Each axis is boxed into a [`MultiProductAxis`], trading a little indirection
+/// for the ability to mix iterator types.
+///
+/// ```
+/// use itertools::MultiProductBuilder;
+///
+/// let it = MultiProductBuilder::new()
+///     .push(0..2)
+///     .push(vec![5, 6])
+///     .push(std::iter::once(9))
+///     .build();
+/// itertools::assert_equal(it, vec![
+///     vec![0, 5, 9],
+///     vec![0, 6, 9],
+///     vec![1, 5, 9],
+///     vec![1, 6, 9],
+/// ]);
+/// ```
+pub struct MultiProductBuilder<'a, T> {
+    axes: Vec<MultiProductAxis<'a, T>>,
+}
+
+impl<'a, T> MultiProductBuilder<'a, T>
+where
+    T: Clone,
+{
+    /// Create a new builder without any axis.
+    pub fn new() -> Self {
+        Self { axes: Vec::new() }
+    }
+
+    /// Add an axis to the product.
+    pub fn push<J>(mut self, iter: J) -> Self
+    where
+        J: IntoIterator<Item = T>,
+        J::IntoIter: Clone + 'a,
+    {
+        self.axes.push(MultiProductAxis(Box::new(iter.into_iter())));
+        self
+    }
+
+    /// Build the cartesian product of the axes, in the order they were pushed.
+    pub fn build(self) -> MultiProduct<MultiProductAxis<'a, T>> {
+        multi_cartesian_product(self.axes.into_iter())
+    }
+}
+
+impl<'a, T> Default for MultiProductBuilder<'a, T>
+where
+    T: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> std::fmt::Debug for MultiProductBuilder<'a, T> {
+    debug_fmt_fields!(MultiProductBuilder, axes);
+}
+
+/// A boxed axis of a [`MultiProduct`] built with [`MultiProductBuilder`].
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultiProductAxis<'a, T>(Box<dyn CloneableIterator<'a, T> + 'a>);
+
+/// An iterator that can be cloned behind a `Box`.
+trait CloneableIterator<'a, T>: Iterator<Item = T> {
+    fn clone_box(&self) -> Box<dyn CloneableIterator<'a, T> + 'a>;
+}
+
+impl<'a, I> CloneableIterator<'a, I::Item> for I
+where
+    I: Iterator + Clone + 'a,
+{
+    fn clone_box(&self) -> Box<dyn CloneableIterator<'a, I::Item> + 'a> {
+        Box::new(self.clone())
+    }
+}
+
+impl<'a, T> Clone for MultiProductAxis<'a, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_box())
+    }
+}
+
+impl<'a, T> std::fmt::Debug for MultiProductAxis<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MultiProductAxis")
+            .field("size_hint", &self.0.size_hint())
+            .finish()
+    }
+}
+
+impl<'a, T> Iterator for MultiProductAxis<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Holds the state of a single iterator within a `MultiProduct`.
 struct MultiProductIter<I>
@@ -78,6 +245,8 @@ where
 {
     iter: I,
     iter_orig: I,
+    /// The only value of this axis once it is fixed, then `iter` is ignored.
+    fixed: Option<I::Item>,
 }
 
 impl<I> MultiProductIter<I>
@@ -89,53 +258,338 @@ where
         Self {
             iter: iter.clone(),
             iter_orig: iter,
+            fixed: None,
+        }
+    }
+
+    /// The first value of this axis.
+    fn first(&mut self) -> Option<I::Item> {
+        match &self.fixed {
+            Some(value) => Some(value.clone()),
+            None => self.iter.next(),
+        }
+    }
+
+    /// Size hint of the values after the current one.
+    fn remaining_hint(&self) -> size_hint::SizeHint {
+        match self.fixed {
+            Some(_) => (0, Some(0)),
+            None => self.iter.size_hint(),
+        }
+    }
+
+    /// Size hint of all the values of this axis.
+    fn len_hint(&self) -> size_hint::SizeHint {
+        match self.fixed {
+            Some(_) => (1, Some(1)),
+            None => self.iter_orig.size_hint(),
         }
     }
 }
 
-impl<I> Iterator for MultiProduct<I>
+impl<I> MultiProduct<I>
 where
     I: Iterator + Clone,
     I::Item: Clone,
 {
-    type Item = Vec<I::Item>;
+    /// Advances to the next product and returns it, without cloning it.
+    fn advance(&mut self) -> Option<&[I::Item]> {
+        self.advance_with_axis().map(|(_, values)| values)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances to the next product and returns it, without cloning it,
+    /// along with the leftmost axis that changed (`0` for the first product).
+    fn advance_with_axis(&mut self) -> Option<(usize, &[I::Item])> {
         // This fuses the iterator.
         let inner = self.0.as_mut()?;
-        match &mut inner.cur {
+        let changed = match &mut inner.cur {
             Populated(values) => {
-                debug_assert!(!inner.iters.is_empty());
+                let mut changed = None;
                 // Find (from the right) a non-finished iterator and
                 // reset the finished ones encountered.
-                for (iter, item) in inner.iters.iter_mut().zip(values.iter_mut()).rev() {
+                for (axis, (iter, item)) in inner
+                    .iters
+                    .iter_mut()
+                    .zip(values.iter_mut())
+                    .enumerate()
+                    .rev()
+                {
+                    if iter.fixed.is_some() {
+                        // A fixed axis never changes.
+                        continue;
+                    }
                     if let Some(new) = iter.iter.next() {
                         *item = new;
-                        return Some(values.clone());
+                        changed = Some(axis);
+                        break;
                     } else {
                         iter.iter = iter.iter_orig.clone();
-                        // `cur` is populated so the untouched `iter_orig` can not be empty.
-                        *item = iter.iter.next().unwrap();
+                        // `cur` is populated so the untouched `iter_orig` should not be empty.
+                        // But its clone might be (e.g. a single-shot source), then the product ends.
+                        if let Some(new) = iter.iter.next() {
+                            *item = new;
+                        } else {
+                            break;
+                        }
                     }
                 }
-                self.0 = ProductEnded;
-                None
+                changed
             }
             // Only the first time.
             NotYetPopulated => {
-                let next: Option<Vec<_>> = inner.iters.iter_mut().map(|i| i.iter.next()).collect();
-                if next.is_none() || inner.iters.is_empty() {
-                    // This cartesian product had at most one item to generate and now ends.
+                inner.cur = inner
+                    .iters
+                    .iter_mut()
+                    .map(MultiProductIter::first)
+                    .collect();
+                inner.cur.as_ref().map(|_| 0)
+            }
+        };
+        match changed {
+            Some(axis) => {
+                let values = self.0.as_ref().and_then(|inner| inner.cur.as_deref())?;
+                Some((axis, values))
+            }
+            None => {
+                self.0 = ProductEnded;
+                None
+            }
+        }
+    }
+
+    /// Returns the number of products of a product that has not started yet,
+    /// computed from the length of each axis without consuming anything.
+    ///
+    /// Returns `None` if the product has started, or if the count overflows a `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = vec![0..3, 0..4, 0..5].into_iter().multi_cartesian_product();
+    /// assert_eq!(it.len_if_exact(), Some(60));
+    /// it.next();
+    /// assert_eq!(it.len_if_exact(), None);
+    /// ```
+    pub fn len_if_exact(&self) -> Option<usize>
+    where
+        I: ExactSizeIterator,
+    {
+        match &self.0 {
+            ProductInProgress(MultiProductInner {
+                iters,
+                cur: NotYetPopulated,
+            }) => iters
+                .iter()
+                .try_fold(1_usize, |product, iter| match iter.fixed {
+                    Some(_) => Some(product),
+                    None => product.checked_mul(iter.iter_orig.len()),
+                }),
+            _ => None,
+        }
+    }
+
+    /// Searches for the first remaining product satisfying `full_pred`, skipping all
+    /// the products sharing a prefix of length `prefix_len` rejected by `prefix_pred`.
+    ///
+    /// This is a backtracking search: `prefix_pred` is called once per prefix, and
+    /// when it returns `false`, `full_pred` is not called on any product with that prefix.
+    /// The first match is the same as with
+    /// `.find(|p| prefix_pred(&p[..prefix_len]) && full_pred(p))`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut nb_checked = 0;
+    /// let found = (0..3).map(|_| 0..10).multi_cartesian_product().find_pruned(
+    ///     2,
+    ///     |prefix| prefix[0] + prefix[1] == 15,
+    ///     |p| {
+    ///         nb_checked += 1;
+    ///         p.iter().sum::<i32>() == 20
+    ///     },
+    /// );
+    /// assert_eq!(found, Some(vec![6, 9, 5]));
+    /// assert_eq!(nb_checked, 6);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix_len` is greater than the number of axes.
+    pub fn find_pruned<P, F>(
+        mut self,
+        prefix_len: usize,
+        mut prefix_pred: P,
+        mut full_pred: F,
+    ) -> Option<Vec<I::Item>>
+    where
+        P: FnMut(&[I::Item]) -> bool,
+        F: FnMut(&[I::Item]) -> bool,
+    {
+        if let Some(inner) = &self.0 {
+            assert!(
+                prefix_len <= inner.iters.len(),
+                "find_pruned: prefix of length {} for a product of {} axes",
+                prefix_len,
+                inner.iters.len()
+            );
+        }
+        let mut prefix_ok = None;
+        while let Some((changed, values)) = self.advance_with_axis() {
+            if prefix_ok.is_none() || changed < prefix_len {
+                prefix_ok = Some(prefix_pred(&values[..prefix_len]));
+            }
+            if prefix_ok == Some(false) {
+                // Exhaust the other axes, so that the prefix changes when advancing.
+                if let Some(inner) = &mut self.0 {
+                    for iter in &mut inner.iters[prefix_len..] {
+                        iter.iter.by_ref().for_each(drop);
+                    }
+                }
+            } else if full_pred(values) {
+                return Some(values.to_vec());
+            }
+        }
+        None
+    }
+
+    /// Returns the last generated product, displayed as a tuple like `(a, b, c)`,
+    /// or `None` before the first product or after the end of the product.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = vec![0..2, 3..5].into_iter().multi_cartesian_product();
+    /// assert!(it.display_current().is_none());
+    /// it.next();
+    /// it.next();
+    /// assert_eq!(it.display_current().unwrap().to_string(), "(0, 4)");
+    /// ```
+    pub fn display_current(&self) -> Option<impl std::fmt::Display + '_>
+    where
+        I::Item: std::fmt::Display,
+    {
+        let values = self.0.as_ref()?.cur.as_deref()?;
+        Some(CurrentTuple(values))
+    }
+
+    /// Returns the lexicographically smallest product, made of the minimum of each axis,
+    /// without enumerating the products.
+    ///
+    /// This is the minimum for the natural lexicographic order of `Vec`, not the minimum by
+    /// a custom key. All the products are considered, including the ones already generated.
+    /// Returns `None` if there are no products at all, or if the product has ended.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![vec![3, 1, 2], vec![5, 4]].into_iter().multi_cartesian_product();
+    /// assert_eq!(it.min_tuple(), Some(vec![1, 4]));
+    /// assert_eq!(it.max_tuple(), Some(vec![3, 5]));
+    /// assert_eq!(it.min_tuple(), it.min());
+    /// ```
+    pub fn min_tuple(&self) -> Option<Vec<I::Item>>
+    where
+        I::Item: Ord,
+    {
+        self.extreme_tuple(Iterator::min)
+    }
+
+    /// Returns the lexicographically largest product, made of the maximum of each axis,
+    /// without enumerating the products.
+    ///
+    /// See [`min_tuple`](MultiProduct::min_tuple) for more information.
+    pub fn max_tuple(&self) -> Option<Vec<I::Item>>
+    where
+        I::Item: Ord,
+    {
+        self.extreme_tuple(Iterator::max)
+    }
+
+    /// Combines the extreme of each axis, given by `extreme`.
+    fn extreme_tuple(&self, extreme: fn(I) -> Option<I::Item>) -> Option<Vec<I::Item>> {
+        let inner = self.0.as_ref()?;
+        inner
+            .iters
+            .iter()
+            .map(|iter| match &iter.fixed {
+                Some(value) => Some(value.clone()),
+                None => extreme(iter.iter_orig.clone()),
+            })
+            .collect()
+    }
+
+    /// Fix the axis `axis` to the single value `value` and restart the product from its beginning.
+    ///
+    /// The other axes are left untouched, so axes can be fixed one after another.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..3).map(|_| 0..2).multi_cartesian_product();
+    /// it.next();
+    /// it.fix_axis(1, 7);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![0, 7, 0],
+    ///     vec![0, 7, 1],
+    ///     vec![1, 7, 0],
+    ///     vec![1, 7, 1],
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is not less than the number of axes,
+    /// or if the product has already ended as its axes are dropped then.
+    pub fn fix_axis(&mut self, axis: usize, value: I::Item) {
+        let inner = self
+            .0
+            .as_mut()
+            .expect("fix_axis: the cartesian product has already ended");
+        let nb_axes = inner.iters.len();
+        assert!(
+            axis < nb_axes,
+            "fix_axis: axis {} is out of range for a product of {} axes",
+            axis,
+            nb_axes
+        );
+        inner.iters[axis].fixed = Some(value);
+        for iter in &mut inner.iters {
+            iter.iter = iter.iter_orig.clone();
+        }
+        inner.cur = NotYetPopulated;
+    }
+}
+
+impl<I> Iterator for MultiProduct<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // With a single axis, there is no other axis to reset: directly delegate to it.
+        if let ProductInProgress(MultiProductInner {
+            iters,
+            cur: Populated(values),
+        }) = &mut self.0
+        {
+            if let ([iter], [value]) = (&mut iters[..], &mut values[..]) {
+                if iter.fixed.is_none() {
+                    if let Some(new) = iter.iter.next() {
+                        *value = new;
+                        return Some(alloc::vec![value.clone()]);
+                    }
                     self.0 = ProductEnded;
-                } else {
-                    inner.cur.clone_from(&next);
+                    return None;
                 }
-                next
             }
         }
+        self.advance().map(<[_]>::to_vec)
     }
 
     fn count(self) -> usize {
+        // Like size hints, the count saturates at `usize::MAX`.
         match self.0 {
             ProductEnded => 0,
             // The iterator is fresh so the count is the product of the length of each iterator:
@@ -146,12 +600,15 @@ where
                 cur: NotYetPopulated,
             }) => iters
                 .into_iter()
-                .map(|iter| iter.iter_orig.count())
-                .try_fold(1, |product, count| {
+                .map(|iter| match iter.fixed {
+                    Some(_) => 1,
+                    None => iter.iter_orig.count(),
+                })
+                .try_fold(1_usize, |product, count| {
                     if count == 0 {
                         None
                     } else {
-                        Some(product * count)
+                        Some(product.saturating_mul(count))
                     }
                 })
                 .unwrap_or_default(),
@@ -159,11 +616,15 @@ where
             ProductInProgress(MultiProductInner {
                 iters,
                 cur: Populated(_),
-            }) => iters.into_iter().fold(0, |mut acc, iter| {
+            }) => iters.into_iter().fold(0_usize, |mut acc, iter| {
+                if iter.fixed.is_some() {
+                    // Only one value, already the current one.
+                    return acc;
+                }
                 if acc != 0 {
-                    acc *= iter.iter_orig.count();
+                    acc = acc.saturating_mul(iter.iter_orig.count());
                 }
-                acc + iter.iter.count()
+                acc.saturating_add(iter.iter.count())
             }),
         }
     }
@@ -176,20 +637,20 @@ where
                 cur: NotYetPopulated,
             }) => iters
                 .iter()
-                .map(|iter| iter.iter_orig.size_hint())
-                .fold((1, Some(1)), size_hint::mul),
+                .map(MultiProductIter::len_hint)
+                .fold((1, Some(1)), size_hint::saturating_mul),
             ProductInProgress(MultiProductInner {
                 iters,
                 cur: Populated(_),
             }) => {
                 if let [first, tail @ ..] = &iters[..] {
-                    tail.iter().fold(first.iter.size_hint(), |mut sh, iter| {
-                        sh = size_hint::mul(sh, iter.iter_orig.size_hint());
-                        size_hint::add(sh, iter.iter.size_hint())
+                    tail.iter().fold(first.remaining_hint(), |mut sh, iter| {
+                        sh = size_hint::saturating_mul(sh, iter.len_hint());
+                        size_hint::saturating_add(sh, iter.remaining_hint())
                     })
                 } else {
-                    // Since it is populated, this cartesian product has started so `iters` is not empty.
-                    unreachable!()
+                    // The nullary cartesian product already generated its only item.
+                    (0, Some(0))
                 }
             }
         }
@@ -204,7 +665,11 @@ where
                 .into_iter()
                 .zip(values)
                 .map(|(i, value)| {
-                    i.iter.last().unwrap_or_else(|| {
+                    let last = match i.fixed {
+                        Some(_) => None,
+                        None => i.iter.last(),
+                    };
+                    last.unwrap_or_else(|| {
                         // The iterator is empty, use its current `value`.
                         count -= 1;
                         value
@@ -218,14 +683,501 @@ where
                 Some(last)
             }
         } else {
-            iters.into_iter().map(|i| i.iter.last()).collect()
+            iters
+                .into_iter()
+                .map(|i| match i.fixed {
+                    Some(value) => Some(value),
+                    None => i.iter.last(),
+                })
+                .collect()
         }
     }
 }
 
+/// The length is exact as long as the number of remaining products fits in a `usize`.
+/// Otherwise, calling `len` panics in debug builds and saturates at `usize::MAX` in release builds.
+impl<I> ExactSizeIterator for MultiProduct<I>
+where
+    I: ExactSizeIterator + Clone,
+    I::Item: Clone,
+{
+    fn len(&self) -> usize {
+        let (lower, upper) = self.size_hint();
+        debug_assert_eq!(upper, Some(lower), "the product length overflows a `usize`");
+        debug_assert!(self.len_if_exact().map_or(true, |len| len == lower));
+        lower
+    }
+}
+
 impl<I> std::iter::FusedIterator for MultiProduct<I>
 where
     I: Iterator + Clone,
     I::Item: Clone,
 {
 }
+
+/// An iterator adaptor that iterates over the cartesian product of multiple iterators,
+/// filling a reused buffer from each product with a closure.
+///
+/// See [`.multi_cartesian_product_fill()`](crate::Itertools::multi_cartesian_product_fill)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultiProductFill<I, F, R>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    product: MultiProduct<I>,
+    f: F,
+    buffer: Vec<R>,
+}
+
+impl<I, F, R> Clone for MultiProductFill<I, F, R>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: Clone,
+    R: Clone,
+{
+    clone_fields!(product, f, buffer);
+}
+
+impl<I, F, R> std::fmt::Debug for MultiProductFill<I, F, R>
+where
+    I: Iterator + Clone + std::fmt::Debug,
+    I::Item: Clone + std::fmt::Debug,
+    R: std::fmt::Debug,
+{
+    debug_fmt_fields!(MultiProductFill, product, buffer);
+}
+
+/// Create a new `MultiProductFill` from an iterator of iterators.
+pub fn multi_cartesian_product_fill<H, F, R>(
+    iters: H,
+    f: F,
+) -> MultiProductFill<<H::Item as IntoIterator>::IntoIter, F, R>
+where
+    H: Iterator,
+    H::Item: IntoIterator,
+    <H::Item as IntoIterator>::IntoIter: Clone,
+    <H::Item as IntoIterator>::Item: Clone,
+    F: FnMut(&[<H::Item as IntoIterator>::Item], &mut Vec<R>),
+{
+    MultiProductFill {
+        product: multi_cartesian_product(iters),
+        f,
+        buffer: Vec::new(),
+    }
+}
+
+impl<I, F, R> MultiProductFill<I, F, R>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: FnMut(&[I::Item], &mut Vec<R>),
+{
+    /// Calls `g` with the buffer filled from each remaining product.
+    ///
+    /// Unlike iterating, which clones the buffer, the buffer is lent to `g`,
+    /// so no allocation is made once it is large enough.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut sums = Vec::new();
+    /// (0..2)
+    ///     .map(|_| 0..3)
+    ///     .multi_cartesian_product_fill(|p, buf| buf.extend(p.iter().map(|x| x * 10)))
+    ///     .for_each_filled(|buf| sums.push(buf.iter().sum::<i32>()));
+    /// assert_eq!(sums, vec![0, 10, 20, 10, 20, 30, 20, 30, 40]);
+    /// ```
+    pub fn for_each_filled<G>(mut self, mut g: G)
+    where
+        G: FnMut(&[R]),
+    {
+        while let Some(values) = self.product.advance() {
+            self.buffer.clear();
+            (self.f)(values, &mut self.buffer);
+            g(&self.buffer);
+        }
+    }
+}
+
+impl<I, F, R> Iterator for MultiProductFill<I, F, R>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: FnMut(&[I::Item], &mut Vec<R>),
+    R: Clone,
+{
+    type Item = Vec<R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let values = self.product.advance()?;
+        self.buffer.clear();
+        (self.f)(values, &mut self.buffer);
+        Some(self.buffer.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.product.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.product.count()
+    }
+}
+
+impl<I, F, R> std::iter::FusedIterator for MultiProductFill<I, F, R>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: FnMut(&[I::Item], &mut Vec<R>),
+    R: Clone,
+{
+}
+
+/// An iterator adaptor that iterates over the cartesian product of multiple iterators
+/// of weighted elements, along with the product of their weights.
+///
+/// See [`.multi_cartesian_product_weighted()`](crate::Itertools::multi_cartesian_product_weighted)
+/// for more information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultiProductWeighted<I>(MultiProduct<I>)
+where
+    I: Iterator + Clone,
+    I::Item: Clone;
+
+impl<I> std::fmt::Debug for MultiProductWeighted<I>
+where
+    I: Iterator + Clone + std::fmt::Debug,
+    I::Item: Clone + std::fmt::Debug,
+{
+    debug_fmt_fields!(MultiProductWeighted, 0);
+}
+
+/// Create a new `MultiProductWeighted` from an iterator of iterators.
+pub fn multi_cartesian_product_weighted<H, T>(
+    iters: H,
+) -> MultiProductWeighted<<H::Item as IntoIterator>::IntoIter>
+where
+    H: Iterator,
+    H::Item: IntoIterator<Item = (T, f64)>,
+    <H::Item as IntoIterator>::IntoIter: Clone,
+    T: Clone,
+{
+    MultiProductWeighted(multi_cartesian_product(iters))
+}
+
+impl<I, T> Iterator for MultiProductWeighted<I>
+where
+    I: Iterator<Item = (T, f64)> + Clone,
+    T: Clone,
+{
+    type Item = (Vec<T>, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let values = self.0.advance()?;
+        let weight = values.iter().map(|(_, weight)| weight).product();
+        let elements = values.iter().map(|(elt, _)| elt.clone()).collect();
+        Some((elements, weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.0.count()
+    }
+}
+
+impl<I, T> std::iter::FusedIterator for MultiProductWeighted<I>
+where
+    I: Iterator<Item = (T, f64)> + Clone,
+    T: Clone,
+{
+}
+
+/// An iterator adaptor that iterates over the cartesian product of multiple iterators,
+/// the first iterator varying the fastest.
+///
+/// See [`.multi_cartesian_product_rev_order()`](crate::Itertools::multi_cartesian_product_rev_order)
+/// for more information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultiProductRevOrder<I>(MultiProduct<I>)
+where
+    I: Iterator + Clone,
+    I::Item: Clone;
+
+impl<I> std::fmt::Debug for MultiProductRevOrder<I>
+where
+    I: Iterator + Clone + std::fmt::Debug,
+    I::Item: Clone + std::fmt::Debug,
+{
+    debug_fmt_fields!(MultiProductRevOrder, 0);
+}
+
+/// Create a new `MultiProductRevOrder` from an iterator of iterators.
+pub fn multi_cartesian_product_rev_order<H>(
+    iters: H,
+) -> MultiProductRevOrder<<H::Item as IntoIterator>::IntoIter>
+where
+    H: Iterator,
+    H::Item: IntoIterator,
+    <H::Item as IntoIterator>::IntoIter: Clone,
+    <H::Item as IntoIterator>::Item: Clone,
+{
+    // The inner product holds the axes in reverse order, its last axis varies the fastest.
+    let iters: Vec<_> = iters.collect();
+    MultiProductRevOrder(multi_cartesian_product(iters.into_iter().rev()))
+}
+
+impl<I> Iterator for MultiProductRevOrder<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let values = self.0.advance()?;
+        Some(values.iter().rev().cloned().collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.0.count()
+    }
+}
+
+impl<I> ExactSizeIterator for MultiProductRevOrder<I>
+where
+    I: ExactSizeIterator + Clone,
+    I::Item: Clone,
+{
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<I> std::iter::FusedIterator for MultiProductRevOrder<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+}
+
+/// An iterator that iterates over the cartesian product of axes created by factories.
+///
+/// See [`multi_cartesian_product_factories`] for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct MultiProductFactories<F, I: Iterator> {
+    factories: Vec<F>,
+    /// The current iterator of each axis, empty before the first product.
+    iters: Vec<I>,
+    cur: Option<Vec<I::Item>>,
+    done: bool,
+}
+
+impl<F, I> std::fmt::Debug for MultiProductFactories<F, I>
+where
+    I: Iterator + std::fmt::Debug,
+    I::Item: std::fmt::Debug,
+{
+    debug_fmt_fields!(MultiProductFactories, iters, cur, done);
+}
+
+/// Return an iterator over the cartesian product of axes, each one created by calling
+/// a factory, instead of cloning iterators like
+/// [`.multi_cartesian_product()`](crate::Itertools::multi_cartesian_product).
+///
+/// The factory of an axis is called when the product starts, then each time the axis
+/// has to restart from its first element, that is once per combination of the values of
+/// the axes to its left. The products are in the same order as with
+/// `.multi_cartesian_product()`.
+///
+/// This does not rely on the `Clone` semantics of the iterators, which might not replay
+/// the same elements (e.g. lines read from a file handle).
+///
+/// ```
+/// use itertools::multi_cartesian_product_factories;
+/// use std::cell::Cell;
+///
+/// let calls = [Cell::new(0), Cell::new(0)];
+/// let it = multi_cartesian_product_factories(calls.iter().map(|calls| {
+///     move || {
+///         calls.set(calls.get() + 1);
+///         0..2
+///     }
+/// }));
+/// itertools::assert_equal(it, vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]]);
+/// assert_eq!([calls[0].get(), calls[1].get()], [1, 2]);
+/// ```
+pub fn multi_cartesian_product_factories<H, F, I>(factories: H) -> MultiProductFactories<F, I>
+where
+    H: IntoIterator<Item = F>,
+    F: FnMut() -> I,
+    I: Iterator,
+    I::Item: Clone,
+{
+    MultiProductFactories {
+        factories: factories.into_iter().collect(),
+        iters: Vec::new(),
+        cur: None,
+        done: false,
+    }
+}
+
+impl<F, I> MultiProductFactories<F, I>
+where
+    F: FnMut() -> I,
+    I: Iterator,
+    I::Item: Clone,
+{
+    /// Advances to the next product. Returns true if there are no more products.
+    fn advance(&mut self) -> bool {
+        let values = match &mut self.cur {
+            Some(values) => values,
+            None => {
+                let mut values = Vec::with_capacity(self.factories.len());
+                for factory in &mut self.factories {
+                    let mut iter = factory();
+                    match iter.next() {
+                        Some(value) => values.push(value),
+                        None => return true,
+                    }
+                    self.iters.push(iter);
+                }
+                self.cur = Some(values);
+                return false;
+            }
+        };
+        // Find (from the right) an axis with a next value...
+        let mut axis = self.iters.len();
+        loop {
+            if axis == 0 {
+                return true;
+            }
+            axis -= 1;
+            if let Some(value) = self.iters[axis].next() {
+                values[axis] = value;
+                break;
+            }
+        }
+        // ...then restart the axes to its right.
+        let axes = self.factories.iter_mut().zip(&mut self.iters);
+        for ((factory, iter), value) in axes.zip(values.iter_mut()).skip(axis + 1) {
+            *iter = factory();
+            match iter.next() {
+                Some(new) => *value = new,
+                None => return true,
+            }
+        }
+        false
+    }
+}
+
+impl<F, I> Iterator for MultiProductFactories<F, I>
+where
+    F: FnMut() -> I,
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.done = self.advance();
+        if self.done {
+            return None;
+        }
+        self.cur.clone()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, None)
+        }
+    }
+}
+
+impl<F, I> std::iter::FusedIterator for MultiProductFactories<F, I>
+where
+    F: FnMut() -> I,
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// Return the rank of `positions` in the lexicographic order of the cartesian product
+/// of axes of the given `lengths`, the last axis varying the fastest.
+///
+/// This is the index of the corresponding item of
+/// [`.multi_cartesian_product()`](crate::Itertools::multi_cartesian_product) when
+/// the `i`-th iterator has `lengths[i]` elements.
+///
+/// Return `None` if the numbers of positions and lengths differ, if a position is out
+/// of its axis, or if the rank would overflow.
+///
+/// ```
+/// use itertools::product_rank;
+///
+/// assert_eq!(product_rank(&[1, 0, 2], &[2, 3, 4]), Some(14));
+/// assert_eq!(product_rank(&[], &[]), Some(0));
+/// assert_eq!(product_rank(&[0, 3], &[2, 3]), None);
+/// ```
+pub fn product_rank(positions: &[usize], lengths: &[usize]) -> Option<usize> {
+    if positions.len() != lengths.len() {
+        return None;
+    }
+    positions
+        .iter()
+        .zip(lengths)
+        .try_fold(0_usize, |rank, (&pos, &len)| {
+            if pos >= len {
+                return None;
+            }
+            rank.checked_mul(len)?.checked_add(pos)
+        })
+}
+
+/// Return the positions on each axis of the item of lexicographic `rank` in the
+/// cartesian product of axes of the given `lengths`, the last axis varying the fastest.
+///
+/// This is the inverse of [`product_rank`].
+///
+/// Return `None` if `rank` is not smaller than the number of items of the product.
+///
+/// ```
+/// use itertools::product_unrank;
+///
+/// assert_eq!(product_unrank(14, &[2, 3, 4]), Some(vec![1, 0, 2]));
+/// assert_eq!(product_unrank(0, &[]), Some(vec![]));
+/// assert_eq!(product_unrank(24, &[2, 3, 4]), None);
+/// ```
+pub fn product_unrank(mut rank: usize, lengths: &[usize]) -> Option<Vec<usize>> {
+    let mut positions = alloc::vec![0; lengths.len()];
+    // Mixed-radix digits, from the least significant one.
+    for (pos, &len) in positions.iter_mut().zip(lengths).rev() {
+        if len == 0 {
+            return None;
+        }
+        *pos = rank % len;
+        rank /= len;
+    }
+    if rank == 0 {
+        Some(positions)
+    } else {
+        None
+    }
+}
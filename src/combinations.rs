@@ -1,10 +1,18 @@
+#[cfg(feature = "use_std")]
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
-use std::iter::FusedIterator;
+#[cfg(feature = "use_std")]
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+use std::iter::{FromIterator, FusedIterator};
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
 
 use super::lazy_buffer::LazyBuffer;
 use alloc::vec::Vec;
 
 use crate::adaptors::checked_binomial;
+use crate::size_hint;
+use crate::{ExactlyOneError, Itertools};
 
 /// An iterator to iterate through all the `k`-length combinations in an iterator.
 ///
@@ -14,6 +22,13 @@ pub struct Combinations<I: Iterator> {
     indices: Vec<usize>,
     pool: LazyBuffer<I>,
     first: bool,
+    /// The items of the current combination, if it was peeked but not yet yielded.
+    peeked: Option<Vec<I::Item>>,
+    /// The start and (exclusive) end of the part of the pool the elements are selected from,
+    /// if restricted. The indices are still relative to the whole pool.
+    window: Option<(usize, usize)>,
+    /// The largest `k` allowed by `reset`, `usize::MAX` if not capped.
+    max_k: usize,
 }
 
 impl<I> Clone for Combinations<I>
@@ -21,7 +36,17 @@ where
     I: Clone + Iterator,
     I::Item: Clone,
 {
-    clone_fields!(indices, pool, first);
+    clone_fields!(indices, pool, first, peeked, window, max_k);
+
+    /// Reuses the allocations of `self`.
+    fn clone_from(&mut self, source: &Self) {
+        self.indices.clone_from(&source.indices);
+        self.pool.clone_from(&source.pool);
+        self.first = source.first;
+        self.peeked.clone_from(&source.peeked);
+        self.window = source.window;
+        self.max_k = source.max_k;
+    }
 }
 
 impl<I> fmt::Debug for Combinations<I>
@@ -29,7 +54,7 @@ where
     I: Iterator + fmt::Debug,
     I::Item: fmt::Debug,
 {
-    debug_fmt_fields!(Combinations, indices, pool, first);
+    debug_fmt_fields!(Combinations, indices, pool, first, peeked, window, max_k);
 }
 
 /// Create a new `Combinations` from a clonable iterator.
@@ -41,6 +66,35 @@ where
         indices: (0..k).collect(),
         pool: LazyBuffer::new(iter),
         first: true,
+        peeked: None,
+        window: None,
+        max_k: usize::MAX,
+    }
+}
+
+impl<T> Combinations<alloc::vec::IntoIter<T>> {
+    /// Create a new `Combinations` of length `k` over the elements of an already buffered `pool`.
+    ///
+    /// The elements are used directly as the pool, without going through a source iterator,
+    /// so [`n`](Combinations::n) is the length of `pool` from the start and size hints are exact.
+    ///
+    /// ```
+    /// use itertools::Combinations;
+    ///
+    /// let it = Combinations::from_pool(vec!['a', 'b', 'c'], 2);
+    /// assert_eq!(it.n(), 3);
+    /// assert_eq!(it.size_hint(), (3, Some(3)));
+    /// itertools::assert_equal(it, vec![vec!['a', 'b'], vec!['a', 'c'], vec!['b', 'c']]);
+    /// ```
+    pub fn from_pool(pool: Vec<T>, k: usize) -> Self {
+        Self {
+            indices: (0..k).collect(),
+            pool: LazyBuffer::from_vec(pool),
+            first: true,
+            peeked: None,
+            window: None,
+            max_k: usize::MAX,
+        }
     }
 }
 
@@ -53,9 +107,181 @@ impl<I: Iterator> Combinations<I> {
 
     /// Returns the (current) length of the pool from which combination elements are
     /// selected. This value can change between invocations of [`next`](Combinations::next).
+    ///
+    /// When the pool is restricted to a window, this is the length of the window.
     #[inline]
     pub fn n(&self) -> usize {
-        self.pool.len()
+        match self.window {
+            Some((start, end)) => end - start,
+            None => self.pool.len(),
+        }
+    }
+
+    /// Restricts the elements combinations are selected from to the ones at the given
+    /// positions in the source iterator, and restarts the iteration.
+    ///
+    /// The window can be moved over the same buffered elements, so this can enumerate
+    /// the combinations of a sliding window without rebuilding the iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..10).combinations(2);
+    /// it.restrict_window(2..=4);
+    /// itertools::assert_equal(it.by_ref(), vec![vec![2, 3], vec![2, 4], vec![3, 4]]);
+    /// it.restrict_window(7..=8);
+    /// itertools::assert_equal(it, vec![vec![7, 8]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window ends before it starts,
+    /// or if it ends after the last element of the source iterator.
+    pub fn restrict_window(&mut self, range: RangeInclusive<usize>) {
+        let start = *range.start();
+        let end = range
+            .end()
+            .checked_add(1)
+            .expect("restrict_window: the window end overflows");
+        assert!(
+            start <= end,
+            "restrict_window: the window starts at {} but ends at {}",
+            start,
+            end - 1
+        );
+        self.pool.prefill(end);
+        assert!(
+            end <= self.pool.len(),
+            "restrict_window: the window ends at {} but the source only has {} elements",
+            end - 1,
+            self.pool.len()
+        );
+        self.window = Some((start, end));
+        self.reset(self.k());
+    }
+
+    /// Returns the position in the pool of the first element combinations can select.
+    #[inline]
+    fn start(&self) -> usize {
+        self.window.map_or(0, |(start, _)| start)
+    }
+
+    /// For a pool of length `pool_len`, returns the count of remaining combinations
+    /// or None if it would overflow.
+    ///
+    /// When restricted to a window, the pool is long enough and `pool_len` is not used.
+    pub(crate) fn remaining(&self, pool_len: usize) -> Option<usize> {
+        match self.window {
+            None => remaining_for(pool_len, self.first, &self.indices),
+            Some((start, end)) if self.first => checked_binomial(end - start, self.k()),
+            Some((_, end)) => remaining_for(end, false, &self.indices),
+        }
+    }
+
+    /// Advances the iterator and writes the next combination into `buf`, instead of
+    /// allocating a new `Vec`.
+    ///
+    /// `buf` is cleared first. Returns `true` if a combination was written, `false` if
+    /// there are no more combinations (then `buf` is left empty).
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (1..5).combinations(3);
+    /// let mut buf = Vec::new();
+    /// let mut sums = Vec::new();
+    /// while it.next_into(&mut buf) {
+    ///     sums.push(buf.iter().sum::<i32>());
+    /// }
+    /// assert_eq!(sums, vec![6, 7, 8, 9]);
+    /// assert!(buf.is_empty());
+    /// ```
+    pub fn next_into(&mut self, buf: &mut Vec<I::Item>) -> bool
+    where
+        I::Item: Clone,
+    {
+        buf.clear();
+        if self.advance() {
+            return false;
+        }
+        buf.extend(self.indices.iter().map(|i| self.pool[*i].clone()));
+        true
+    }
+
+    /// Returns a reference to the next combination without advancing the iterator.
+    ///
+    /// The combination is computed once and then yielded by the next call to
+    /// [`next`](Combinations::next), without cloning its elements again.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (1..4).combinations(2);
+    /// assert_eq!(it.peek(), Some(&[1, 2][..]));
+    /// assert_eq!(it.size_hint(), (3, Some(3)));
+    /// assert_eq!(it.next(), Some(vec![1, 2]));
+    /// assert_eq!(it.next(), Some(vec![1, 3]));
+    /// assert_eq!(it.peek(), Some(&[2, 3][..]));
+    /// assert_eq!(it.next(), Some(vec![2, 3]));
+    /// assert_eq!(it.peek(), None);
+    /// ```
+    pub fn peek(&mut self) -> Option<&[I::Item]>
+    where
+        I::Item: Clone,
+    {
+        if self.peeked.is_none() && !self.advance() {
+            self.peeked = Some(self.pool.get_at(&self.indices));
+        }
+        self.peeked.as_deref()
+    }
+
+    /// Takes one more element from the source and returns an iterator over the new
+    /// combinations it makes, or `None` if the source is exhausted.
+    ///
+    /// The new combinations are the ones that include the new element, together with
+    /// elements taken from the source before it. Polling until `None` therefore yields
+    /// every combination exactly once, in the order of
+    /// [`.combinations_colex(k)`](crate::Itertools::combinations_colex),
+    /// except for the empty combination which never includes a new element.
+    ///
+    /// This does not change the combinations left to [`next`](Combinations::next),
+    /// only how much of the source is buffered.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = "abc".chars().combinations(2);
+    /// itertools::assert_equal(it.poll_new().unwrap(), Vec::<Vec<char>>::new());
+    /// itertools::assert_equal(it.poll_new().unwrap(), vec![vec!['a', 'b']]);
+    /// itertools::assert_equal(it.poll_new().unwrap(), vec![vec!['a', 'c'], vec!['b', 'c']]);
+    /// assert!(it.poll_new().is_none());
+    /// ```
+    pub fn poll_new(&mut self) -> Option<CombinationsPollNew<'_, I>> {
+        if !self.pool.get_next() {
+            return None;
+        }
+        let new = self.pool.len() - 1;
+        // The other elements of a new combination.
+        let others = self.k().checked_sub(1);
+        Some(CombinationsPollNew {
+            pool: &self.pool,
+            indices: (0..others.unwrap_or_default()).collect(),
+            first: true,
+            done: others.map_or(true, |others| others > new),
+        })
+    }
+
+    /// The size hint of the remaining combinations, without requiring cloneable items.
+    pub(crate) fn remaining_hint(&self) -> (usize, Option<usize>) {
+        let (mut low, mut upp) = match self.window {
+            // The window is entirely in the pool.
+            Some(_) => (0, Some(0)),
+            None => self.pool.size_hint(),
+        };
+        low = self.remaining(low).unwrap_or(usize::MAX);
+        upp = upp.and_then(|upp| self.remaining(upp));
+        let peeked = usize::from(self.peeked.is_some());
+        size_hint::add_scalar((low, upp), peeked)
     }
 
     /// Returns a reference to the source pool.
@@ -64,35 +290,349 @@ impl<I: Iterator> Combinations<I> {
         &self.pool
     }
 
+    /// Caps the length `k` of the combinations to `max_k`, which must be at least
+    /// the current `k`.
+    ///
+    /// `reset` then clamps `k` to `max_k`, so it never requires more than `max_k`
+    /// elements from the source: growing `k` can not pull from an infinite source forever.
+    pub(crate) fn with_max_k(mut self, max_k: usize) -> Self {
+        debug_assert!(self.k() <= max_k);
+        self.max_k = max_k;
+        self
+    }
+
+    /// Returns the largest `k` allowed by `reset`, `usize::MAX` if not capped.
+    #[inline]
+    pub(crate) fn max_k(&self) -> usize {
+        self.max_k
+    }
+
     /// Resets this `Combinations` back to an initial state for combinations of length
     /// `k` over the same pool data source. If `k` is larger than the current length
     /// of the data pool an attempt is made to prefill the pool so that it holds `k`
     /// elements.
+    ///
+    /// `k` is clamped to the cap set by `with_max_k`.
     pub(crate) fn reset(&mut self, k: usize) {
+        let k = k.min(self.max_k);
         self.first = true;
+        self.peeked = None;
+        let start = self.start();
 
         if k < self.indices.len() {
             self.indices.truncate(k);
             for i in 0..k {
-                self.indices[i] = i;
+                self.indices[i] = start + i;
             }
         } else {
             for i in 0..self.indices.len() {
-                self.indices[i] = i;
+                self.indices[i] = start + i;
+            }
+            self.indices.extend(start + self.indices.len()..start + k);
+            self.pool.prefill(start + k);
+        }
+    }
+
+    /// Attempts to fill the pool so that it holds `len` elements.
+    pub(crate) fn prefill_pool(&mut self, len: usize) {
+        self.pool.prefill(len);
+    }
+
+    /// Sets the current combination to the one of the given `rank` (in lexicographic order)
+    /// among all the combinations of the pool, as if the previous ones had been generated.
+    ///
+    /// The pool should be fully buffered and `rank` less than `binomial(n, k)`.
+    pub(crate) fn jump_to(&mut self, rank: usize) {
+        unrank(self.n(), rank, &mut self.indices);
+        let start = self.start();
+        for index in &mut self.indices {
+            *index += start;
+        }
+        self.first = false;
+    }
+
+    /// Sets the current combination to the last one of the pool,
+    /// as if all the combinations had been generated.
+    ///
+    /// The pool should be fully buffered and hold at least `k` elements.
+    pub(crate) fn jump_to_last(&mut self) {
+        let first = self.start() + self.n() - self.k();
+        for (i, index) in self.indices.iter_mut().enumerate() {
+            *index = first + i;
+        }
+        self.first = false;
+    }
+
+    /// Sets the current combination to the first one starting with the element at position
+    /// `first` (relative to the window), as if it had been peeked.
+    ///
+    /// Only the elements this combination needs are taken from the source.
+    /// Returns false, leaving the state untouched, if there is no such combination.
+    pub(crate) fn jump_to_group(&mut self, first: usize) -> bool
+    where
+        I::Item: Clone,
+    {
+        if self.k() == 0 {
+            return false;
+        }
+        if self.window.is_none() {
+            self.pool.prefill(first + self.k());
+        }
+        if first + self.k() > self.n() {
+            return false;
+        }
+        let start = self.start();
+        for (i, index) in self.indices.iter_mut().enumerate() {
+            *index = start + first + i;
+        }
+        self.first = false;
+        self.peeked = Some(self.pool.get_at(&self.indices));
+        true
+    }
+
+    /// Returns the combination `rank` steps ahead, as [`nth`](Iterator::nth) would,
+    /// but without advancing the iterator.
+    ///
+    /// This buffers the entire source iterator, then computes the combination directly
+    /// from its rank, without generating the ones before it.
+    /// It returns `None` if there are less than `rank + 1` remaining combinations,
+    /// or if the total number of combinations overflows a `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..5).combinations(3);
+    /// assert_eq!(it.get_by_rank(4), Some(vec![0, 2, 4]));
+    /// assert_eq!(it.get_by_rank(10), None);
+    /// it.next();
+    /// assert_eq!(it.get_by_rank(4), Some(vec![0, 3, 4]));
+    /// assert_eq!(it.next(), Some(vec![0, 1, 3]));
+    /// ```
+    pub fn get_by_rank(&mut self, rank: usize) -> Option<Vec<I::Item>>
+    where
+        I::Item: Clone,
+    {
+        self.pool.prefill(usize::MAX);
+        let total = checked_binomial(self.n(), self.k())?;
+        let (remaining, _) = self.size_hint();
+        let target = (total - remaining).checked_add(rank)?;
+        if target >= total {
+            return None;
+        }
+        let mut indices = alloc::vec![0; self.k()];
+        unrank(self.n(), target, &mut indices);
+        let start = self.start();
+        for index in &mut indices {
+            *index += start;
+        }
+        Some(self.pool.get_at(&indices))
+    }
+
+    /// Returns the number of remaining combinations before the first one for which
+    /// `pred` returns `false`, without advancing the iterator.
+    ///
+    /// Like [`slice::partition_point`], `pred` must be monotone over the remaining
+    /// combinations (all the ones for which it returns `true` come first), and a binary
+    /// search is used: the source is buffered entirely, then only `O(log(remaining))`
+    /// combinations are computed directly from their rank and passed to `pred`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..10).combinations(3);
+    /// // The combinations starting before 7 come first.
+    /// let count = it.partition_point_rank(|c| c[0] < 7);
+    /// assert_eq!(count, 119);
+    /// assert_eq!(it.nth(count), Some(vec![7, 8, 9]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total number of combinations overflows a `usize`.
+    pub fn partition_point_rank<F>(&mut self, mut pred: F) -> usize
+    where
+        I::Item: Clone,
+        F: FnMut(&[I::Item]) -> bool,
+    {
+        self.pool.prefill(usize::MAX);
+        let total = checked_binomial(self.n(), self.k())
+            .expect("the number of combinations overflows a usize");
+        let (remaining, _) = self.size_hint();
+        let offset = total - remaining;
+        let start = self.start();
+        let mut indices = alloc::vec![0; self.k()];
+        let mut buffer = Vec::with_capacity(self.k());
+        let (mut low, mut high) = (0, remaining);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            unrank(self.n(), offset + mid, &mut indices);
+            buffer.clear();
+            buffer.extend(indices.iter().map(|i| self.pool[start + i].clone()));
+            if pred(&buffer) {
+                low = mid + 1;
+            } else {
+                high = mid;
             }
-            self.indices.extend(self.indices.len()..k);
-            self.pool.prefill(k);
         }
+        low
+    }
+
+    /// Returns the remaining combination if there is exactly one of them,
+    /// like [`exactly_one`](crate::Itertools::exactly_one).
+    ///
+    /// This is typically the case when `k` is `0` or the length of the source.
+    /// Otherwise, an error is returned containing an iterator over the remaining combinations.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert_eq!((1..4).combinations(3).single().unwrap(), vec![1, 2, 3]);
+    /// assert_eq!((1..4).combinations(0).single().unwrap(), vec![]);
+    /// assert!((1..4).combinations(2).single().unwrap_err().eq((1..4).combinations(2)));
+    /// assert_eq!((1..4).combinations(4).single().unwrap_err().count(), 0);
+    /// ```
+    #[allow(clippy::result_large_err)] // Like `exactly_one`, the error holds the iterator.
+    pub fn single(self) -> Result<Vec<I::Item>, ExactlyOneError<Self>>
+    where
+        I::Item: Clone,
+    {
+        self.exactly_one()
+    }
+
+    /// Returns an iterator adaptor that calls `f` with the new length of the pool each time
+    /// an element is taken from the source, while iterating over the combinations.
+    ///
+    /// `f` is called exactly once per newly buffered element, right after the step that
+    /// buffered it, and never for the elements already buffered.
+    /// This can be used to report the progress over a streaming source.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut lens = Vec::new();
+    /// let it = (1..5).combinations(2).on_pool_grow(|len| lens.push(len));
+    /// assert_eq!(it.count(), 6);
+    /// assert_eq!(lens, vec![1, 2, 3, 4]);
+    /// ```
+    pub fn on_pool_grow<F>(self, f: F) -> CombinationsOnPoolGrow<I, F>
+    where
+        F: FnMut(usize),
+    {
+        CombinationsOnPoolGrow {
+            pool_len: self.pool.len(),
+            combs: self,
+            f,
+        }
+    }
+
+    /// Returns an iterator over the groups of consecutive remaining combinations that
+    /// start with the same element, along with the position of that element in the pool.
+    ///
+    /// The combinations starting with the element at position `first` make a group of
+    /// `binomial(n - 1 - first, k - 1)` combinations when the iterator is fresh.
+    /// Concatenating the groups gives back the combinations.
+    /// The only combination of length `0` is in a group of position `0`.
+    ///
+    /// Each group is a lazy iterator over its own clone of the combinations, so the groups
+    /// can be processed independently, e.g. in parallel. Moving to the next group only takes
+    /// from the source the elements its first combination needs, even if the previous
+    /// groups were not consumed: the source can be infinite.
+    ///
+    /// This independence has a cost: each group clones the source iterator and every element
+    /// taken from it so far, hence the `I: Clone` bound. Going through the groups of `n`
+    /// elements clones `O(n)` elements per group, `O(n²)` in total. If the groups are
+    /// consumed one after the other anyway, iterating the combinations directly is cheaper.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut groups = "abcd".chars().combinations(2).group_by_first();
+    /// let (first, group) = groups.next().unwrap();
+    /// assert_eq!(first, 0);
+    /// itertools::assert_equal(group, vec![vec!['a', 'b'], vec!['a', 'c'], vec!['a', 'd']]);
+    /// let sizes: Vec<_> = groups.map(|(first, group)| (first, group.count())).collect();
+    /// assert_eq!(sizes, vec![(1, 2), (2, 1)]);
+    ///
+    /// let (first, group) = (0..).combinations(2).group_by_first().nth(3).unwrap();
+    /// assert_eq!(first, 3);
+    /// itertools::assert_equal(group.take(2), vec![vec![3, 4], vec![3, 5]]);
+    /// ```
+    pub fn group_by_first(self) -> CombinationsGroupByFirst<I> {
+        CombinationsGroupByFirst {
+            combs: self,
+            done: false,
+        }
+    }
+
+    /// Folds every remaining combination into an accumulator, passing `f` the positions
+    /// in the source of the elements of the combination along with the elements themselves.
+    ///
+    /// The elements are cloned into a single buffer reused for all combinations,
+    /// so no vector is allocated per combination.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let positions = "abcd".chars().combinations(2).fold_indices(
+    ///     Vec::new(),
+    ///     |mut acc, indices, items| {
+    ///         if items.contains(&'c') {
+    ///             acc.push(indices.to_vec());
+    ///         }
+    ///         acc
+    ///     },
+    /// );
+    /// assert_eq!(positions, vec![vec![0, 2], vec![1, 2], vec![2, 3]]);
+    /// ```
+    pub fn fold_indices<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        I::Item: Clone,
+        F: FnMut(B, &[usize], &[I::Item]) -> B,
+    {
+        let mut acc = init;
+        let mut buffer = Vec::with_capacity(self.k());
+        while !self.advance() {
+            buffer.clear();
+            buffer.extend(self.indices.iter().map(|&i| self.pool[i].clone()));
+            acc = f(acc, &self.indices, &buffer);
+        }
+        acc
+    }
+
+    /// Fills the pool with all elements of the source iterator then sorts it by key.
+    pub(crate) fn sort_pool_by_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: FnMut(&I::Item) -> K,
+    {
+        self.pool.prefill(usize::MAX);
+        self.pool.sort_by_key(f);
     }
 
     pub(crate) fn n_and_count(self) -> (usize, usize) {
+        let (n, count) = self.n_and_checked_count();
+        (n, count.unwrap())
+    }
+
+    /// Like `n_and_count`, but the count is `None` if it overflows a `usize`.
+    pub(crate) fn n_and_checked_count(self) -> (usize, Option<usize>) {
+        if self.window.is_some() {
+            let count = self.remaining(0);
+            let peeked = usize::from(self.peeked.is_some());
+            return (self.n(), count.and_then(|count| count.checked_add(peeked)));
+        }
         let Self {
             indices,
             pool,
             first,
+            peeked,
+            window: _,
+            max_k: _,
         } = self;
         let n = pool.count();
-        (n, remaining_for(n, first, &indices).unwrap())
+        let count = remaining_for(n, first, &indices);
+        let peeked = usize::from(peeked.is_some());
+        (n, count.and_then(|count| count.checked_add(peeked)))
     }
 
     /// Initialises the iterator by filling a buffer with elements from the
@@ -117,15 +657,20 @@ impl<I: Iterator> Combinations<I> {
             return true; // Done
         }
 
+        // Only reachable once `init` succeeded (or after `jump_to`), so the pool holds
+        // at least `k` elements and the subtractions below can not underflow.
+        debug_assert!(!self.first && self.indices.len() <= self.n());
+
         // Scan from the end, looking for an index to increment
         let mut i: usize = self.indices.len() - 1;
 
         // Check if we need to consume more from the iterator
-        if self.indices[i] == self.pool.len() - 1 {
+        if self.window.is_none() && self.indices[i] == self.pool.len() - 1 {
             self.pool.get_next(); // may change pool size
         }
 
-        while self.indices[i] == i + self.pool.len() - self.indices.len() {
+        let end = self.start() + self.n();
+        while self.indices[i] == i + end - self.indices.len() {
             if i > 0 {
                 i -= 1;
             } else {
@@ -144,17 +689,47 @@ impl<I: Iterator> Combinations<I> {
         false
     }
 
+    /// Advances to the next combination.
+    ///
+    /// Returns true if we've run out of combinations, false otherwise.
+    pub(crate) fn advance(&mut self) -> bool {
+        if self.peeked.take().is_some() {
+            // The current combination was peeked but not yielded yet.
+            return false;
+        }
+        if self.first {
+            self.init()
+        } else {
+            self.increment_indices()
+        }
+    }
+
+    /// Returns true if the current indices are the ones of the last yielded combination,
+    /// false if no combination has been yielded since the last reset.
+    ///
+    /// Peeked combinations are considered yielded.
+    #[inline]
+    pub(crate) fn is_started(&self) -> bool {
+        !self.first
+    }
+
+    /// Returns the pool indices of the current combination.
+    #[inline]
+    pub(crate) fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
     /// Returns the n-th item or the number of successful steps.
     pub(crate) fn try_nth(&mut self, n: usize) -> Result<<Self as Iterator>::Item, usize>
     where
         I::Item: Clone,
     {
-        let done = if self.first {
-            self.init()
-        } else {
-            self.increment_indices()
-        };
-        if done {
+        if n == 0 {
+            if let Some(item) = self.peeked.take() {
+                return Ok(item);
+            }
+        }
+        if self.advance() {
             return Err(0);
         }
         for i in 0..n {
@@ -173,13 +748,10 @@ where
 {
     type Item = Vec<I::Item>;
     fn next(&mut self) -> Option<Self::Item> {
-        let done = if self.first {
-            self.init()
-        } else {
-            self.increment_indices()
-        };
-
-        if done {
+        if let Some(item) = self.peeked.take() {
+            return Some(item);
+        }
+        if self.advance() {
             return None;
         }
 
@@ -191,10 +763,7 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (mut low, mut upp) = self.pool.size_hint();
-        low = remaining_for(low, self.first, &self.indices).unwrap_or(usize::MAX);
-        upp = upp.and_then(|upp| remaining_for(upp, self.first, &self.indices));
-        (low, upp)
+        self.remaining_hint()
     }
 
     #[inline]
@@ -210,34 +779,1682 @@ where
 {
 }
 
-/// For a given size `n`, return the count of remaining combinations or None if it would overflow.
-fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
-    let k = indices.len();
-    if n < k {
-        Some(0)
-    } else if first {
-        checked_binomial(n, k)
-    } else {
-        // https://en.wikipedia.org/wiki/Combinatorial_number_system
-        // http://www.site.uottawa.ca/~lucia/courses/5165-09/GenCombObj.pdf
+/// Iterates over the remaining combinations of a clone, from the current state
+/// which is not necessarily the beginning, leaving the original untouched.
+///
+/// ```
+/// use itertools::Itertools;
+///
+/// let mut combs = (1..4).combinations(2);
+/// combs.next();
+/// for _ in 0..2 {
+///     let mut sums = Vec::new();
+///     for c in &combs {
+///         sums.push(c.iter().sum::<i32>());
+///     }
+///     assert_eq!(sums, vec![4, 5]);
+/// }
+/// ```
+impl<I> IntoIterator for &Combinations<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+    type IntoIter = Combinations<I>;
 
-        // The combinations generated after the current one can be counted by counting as follows:
-        // - The subsequent combinations that differ in indices[0]:
-        //   If subsequent combinations differ in indices[0], then their value for indices[0]
-        //   must be at least 1 greater than the current indices[0].
-        //   As indices is strictly monotonically sorted, this means we can effectively choose k values
-        //   from (n - 1 - indices[0]), leading to binomial(n - 1 - indices[0], k) possibilities.
-        // - The subsequent combinations with same indices[0], but differing indices[1]:
-        //   Here we can choose k - 1 values from (n - 1 - indices[1]) values,
-        //   leading to binomial(n - 1 - indices[1], k - 1) possibilities.
-        // - (...)
-        // - The subsequent combinations with same indices[0..=i], but differing indices[i]:
-        //   Here we can choose k - i values from (n - 1 - indices[i]) values: binomial(n - 1 - indices[i], k - i).
-        //   Since subsequent combinations can in any index, we must sum up the aforementioned binomial coefficients.
+    fn into_iter(self) -> Self::IntoIter {
+        self.clone()
+    }
+}
 
-        // Below, `n0` resembles indices[i].
-        indices.iter().enumerate().try_fold(0usize, |sum, (i, n0)| {
-            sum.checked_add(checked_binomial(n - 1 - *n0, k - i)?)
-        })
+/// An iterator over the combinations including the last element taken from the source.
+///
+/// See [`Combinations::poll_new`] for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsPollNew<'a, I: Iterator> {
+    pool: &'a LazyBuffer<I>,
+    /// The indices of the other elements of the current combination.
+    indices: Vec<usize>,
+    first: bool,
+    done: bool,
+}
+
+impl<'a, I> Clone for CombinationsPollNew<'a, I>
+where
+    I: Iterator,
+{
+    clone_fields!(pool, indices, first, done);
+}
+
+impl<'a, I> fmt::Debug for CombinationsPollNew<'a, I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsPollNew, pool, indices, first, done);
+}
+
+impl<'a, I> Iterator for CombinationsPollNew<'a, I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let new = self.pool.len() - 1;
+        if self.first {
+            self.first = false;
+        } else {
+            // Increment the other indices in co-lexicographic order, below the new element.
+            let len = self.indices.len();
+            let i = (0..len).find(|&i| {
+                let upper = self.indices.get(i + 1).copied().unwrap_or(new);
+                self.indices[i] + 1 < upper
+            });
+            match i {
+                Some(i) => {
+                    self.indices[i] += 1;
+                    for (j, index) in self.indices[..i].iter_mut().enumerate() {
+                        *index = j;
+                    }
+                }
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            }
+        }
+        let mut combination = Vec::with_capacity(self.indices.len() + 1);
+        combination.extend(self.indices.iter().map(|i| self.pool[*i].clone()));
+        combination.push(self.pool[new].clone());
+        Some(combination)
     }
 }
+
+impl<'a, I> FusedIterator for CombinationsPollNew<'a, I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator adaptor that maps the `k`-length combinations of an iterator
+/// while a closure returns `Some`.
+///
+/// See [`.combinations_map_while()`](crate::Itertools::combinations_map_while) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsMapWhile<I: Iterator, F> {
+    combs: Combinations<I>,
+    buffer: Vec<I::Item>,
+    f: F,
+    done: bool,
+}
+
+impl<I, F> Clone for CombinationsMapWhile<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(combs, buffer, f, done);
+}
+
+impl<I, F> fmt::Debug for CombinationsMapWhile<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsMapWhile, combs, done);
+}
+
+/// Create a new `CombinationsMapWhile` from a clonable iterator.
+pub fn combinations_map_while<I, F, R>(iter: I, k: usize, f: F) -> CombinationsMapWhile<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item]) -> Option<R>,
+{
+    CombinationsMapWhile {
+        combs: combinations(iter, k),
+        buffer: Vec::with_capacity(k),
+        f,
+        done: false,
+    }
+}
+
+impl<I, F, R> Iterator for CombinationsMapWhile<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item]) -> Option<R>,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.combs.advance() {
+            self.done = true;
+            return None;
+        }
+        let pool = self.combs.src();
+        self.buffer.clear();
+        self.buffer
+            .extend(self.combs.indices().iter().map(|i| pool[*i].clone()));
+        let item = (self.f)(&self.buffer);
+        self.done = item.is_none();
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.combs.size_hint().1)
+        }
+    }
+}
+
+impl<I, F, R> FusedIterator for CombinationsMapWhile<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item]) -> Option<R>,
+{
+}
+
+/// An iterator adaptor that iterates through the `k`-length combinations of an iterator,
+/// removing the consecutive ones deemed the same.
+///
+/// See [`.combinations_dedup_by()`](crate::Itertools::combinations_dedup_by) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsDedupBy<I: Iterator, F> {
+    combs: Combinations<I>,
+    /// The last combination yielded.
+    last: Option<Vec<I::Item>>,
+    buffer: Vec<I::Item>,
+    same: F,
+}
+
+impl<I, F> Clone for CombinationsDedupBy<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(combs, last, buffer, same);
+}
+
+impl<I, F> fmt::Debug for CombinationsDedupBy<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsDedupBy, combs, last);
+}
+
+/// Create a new `CombinationsDedupBy` from a clonable iterator.
+pub fn combinations_dedup_by<I, F>(iter: I, k: usize, same: F) -> CombinationsDedupBy<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item], &[I::Item]) -> bool,
+{
+    CombinationsDedupBy {
+        combs: combinations(iter, k),
+        last: None,
+        buffer: Vec::with_capacity(k),
+        same,
+    }
+}
+
+impl<I, F> Iterator for CombinationsDedupBy<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item], &[I::Item]) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.combs.next_into(&mut self.buffer) {
+            match &mut self.last {
+                // Removed combinations are only written in the reused buffer.
+                Some(last) if (self.same)(last, &self.buffer) => continue,
+                Some(last) => std::mem::swap(last, &mut self.buffer),
+                None => self.last = Some(std::mem::take(&mut self.buffer)),
+            }
+            return self.last.clone();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, upp) = self.combs.size_hint();
+        // All remaining combinations might be the same as the last one.
+        let low = if self.last.is_none() { low.min(1) } else { 0 };
+        (low, upp)
+    }
+}
+
+impl<I, F> FusedIterator for CombinationsDedupBy<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item], &[I::Item]) -> bool,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations of an iterator,
+/// calling a closure when the pool grows.
+///
+/// See [`Combinations::on_pool_grow`] for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsOnPoolGrow<I: Iterator, F> {
+    combs: Combinations<I>,
+    /// The length of the pool already reported.
+    pool_len: usize,
+    f: F,
+}
+
+impl<I, F> Clone for CombinationsOnPoolGrow<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(combs, pool_len, f);
+}
+
+impl<I, F> fmt::Debug for CombinationsOnPoolGrow<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsOnPoolGrow, combs, pool_len);
+}
+
+impl<I, F> CombinationsOnPoolGrow<I, F>
+where
+    I: Iterator,
+    F: FnMut(usize),
+{
+    /// Calls `f` for each element buffered since the last call.
+    fn report(&mut self) {
+        let len = self.combs.pool.len();
+        for pool_len in self.pool_len + 1..=len {
+            (self.f)(pool_len);
+        }
+        self.pool_len = len;
+    }
+}
+
+impl<I, F> Iterator for CombinationsOnPoolGrow<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(usize),
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.combs.next();
+        self.report();
+        item
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let item = self.combs.nth(n);
+        self.report();
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+}
+
+impl<I, F> FusedIterator for CombinationsOnPoolGrow<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(usize),
+{
+}
+
+/// An iterator over the groups of the `k`-length combinations of an iterator
+/// that start with the same element.
+///
+/// See [`Combinations::group_by_first`] for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsGroupByFirst<I: Iterator> {
+    combs: Combinations<I>,
+    done: bool,
+}
+
+impl<I> Clone for CombinationsGroupByFirst<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs, done);
+}
+
+impl<I> fmt::Debug for CombinationsGroupByFirst<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsGroupByFirst, combs, done);
+}
+
+impl<I> Iterator for CombinationsGroupByFirst<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    type Item = (usize, CombinationsFirstGroup<I>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // Peeking sets the indices to the ones of the first combination of the group.
+        if self.combs.peek().is_none() {
+            self.done = true;
+            return None;
+        }
+        let start = self.combs.start();
+        let first = self
+            .combs
+            .indices()
+            .first()
+            .map_or(0, |first| first - start);
+        let group = CombinationsFirstGroup {
+            combs: self.combs.clone(),
+            first,
+        };
+        self.done = !self.combs.jump_to_group(first + 1);
+        Some((first, group))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let (low, upp) = self.combs.size_hint();
+        (low.min(1), upp)
+    }
+}
+
+impl<I> FusedIterator for CombinationsGroupByFirst<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+}
+
+/// A lazy iterator over the `k`-length combinations of an iterator
+/// that start with the same element.
+///
+/// See [`Combinations::group_by_first`] for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsFirstGroup<I: Iterator> {
+    combs: Combinations<I>,
+    /// The position of the first element of the combinations, relative to the window.
+    first: usize,
+}
+
+impl<I> Clone for CombinationsFirstGroup<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs, first);
+}
+
+impl<I> fmt::Debug for CombinationsFirstGroup<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsFirstGroup, combs, first);
+}
+
+impl<I> Iterator for CombinationsFirstGroup<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.combs.k() > 0 {
+            // The group ends when the next combination starts with another element.
+            self.combs.peek()?;
+            if self.combs.indices()[0] - self.combs.start() != self.first {
+                return None;
+            }
+        }
+        self.combs.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.combs.size_hint().1)
+    }
+}
+
+impl<I> FusedIterator for CombinationsFirstGroup<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations of an iterator,
+/// each one flagged with whether it is the last one.
+///
+/// See [`.combinations_flagged()`](crate::Itertools::combinations_flagged) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsFlagged<I: Iterator> {
+    combs: Combinations<I>,
+}
+
+impl<I> Clone for CombinationsFlagged<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs);
+}
+
+impl<I> fmt::Debug for CombinationsFlagged<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsFlagged, combs);
+}
+
+/// Create a new `CombinationsFlagged` from a clonable iterator.
+pub fn combinations_flagged<I: Iterator>(iter: I, k: usize) -> CombinationsFlagged<I> {
+    CombinationsFlagged {
+        combs: combinations(iter, k),
+    }
+}
+
+impl<I> CombinationsFlagged<I>
+where
+    I: Iterator,
+{
+    /// Returns true if the current combination is the last one.
+    ///
+    /// When the indices are the last ones of the pool, this takes one more element
+    /// from the source to know if it is exhausted, as the next step would.
+    fn is_last(&mut self) -> bool {
+        let (n, k) = (self.combs.n(), self.combs.k());
+        if k == 0 {
+            return true;
+        }
+        let at_end =
+            (self.combs.indices().iter().enumerate()).all(|(i, &index)| index == n - k + i);
+        if !at_end {
+            return false;
+        }
+        self.combs.prefill_pool(n + 1);
+        self.combs.n() == n
+    }
+}
+
+impl<I> Iterator for CombinationsFlagged<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (Vec<I::Item>, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.combs.advance() {
+            return None;
+        }
+        let combination = self.combs.src().get_at(self.combs.indices());
+        Some((combination, self.is_last()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.count()
+    }
+}
+
+impl<I> FusedIterator for CombinationsFlagged<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations of
+/// the `Copy` elements of an iterator.
+///
+/// See [`.combinations_copied()`](crate::Itertools::combinations_copied) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsCopied<I: Iterator> {
+    combs: Combinations<I>,
+}
+
+impl<I> Clone for CombinationsCopied<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs);
+}
+
+impl<I> fmt::Debug for CombinationsCopied<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsCopied, combs);
+}
+
+/// Create a new `CombinationsCopied` from an iterator of `Copy` elements.
+pub fn combinations_copied<I>(iter: I, k: usize) -> CombinationsCopied<I>
+where
+    I: Iterator,
+    I::Item: Copy,
+{
+    CombinationsCopied {
+        combs: combinations(iter, k),
+    }
+}
+
+impl<I> Iterator for CombinationsCopied<I>
+where
+    I: Iterator,
+    I::Item: Copy,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.combs.advance() {
+            return None;
+        }
+        Some(self.combs.src().get_at_copied(self.combs.indices()))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            if self.combs.advance() {
+                return None;
+            }
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.count()
+    }
+}
+
+impl<I> FusedIterator for CombinationsCopied<I>
+where
+    I: Iterator,
+    I::Item: Copy,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations of the elements
+/// of an iterator, sorted by key.
+///
+/// See [`.combinations_by_key()`](crate::Itertools::combinations_by_key) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsByKey<I: Iterator, F> {
+    combs: Combinations<I>,
+    /// `None` once the pool is sorted.
+    key: Option<F>,
+}
+
+impl<I, F> Clone for CombinationsByKey<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(combs, key);
+}
+
+impl<I, F> fmt::Debug for CombinationsByKey<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsByKey, combs);
+}
+
+/// Create a new `CombinationsByKey` from a clonable iterator.
+pub fn combinations_by_key<I, F, K>(iter: I, k: usize, key: F) -> CombinationsByKey<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item) -> K,
+    K: Ord,
+{
+    CombinationsByKey {
+        combs: combinations(iter, k),
+        key: Some(key),
+    }
+}
+
+impl<I, F> CombinationsByKey<I, F>
+where
+    I: Iterator,
+{
+    /// Sorts the pool, if not done yet.
+    fn sort_pool<K>(&mut self)
+    where
+        F: FnMut(&I::Item) -> K,
+        K: Ord,
+    {
+        if let Some(key) = self.key.take() {
+            self.combs.sort_pool_by_key(key);
+        }
+    }
+}
+
+impl<I, F, K> Iterator for CombinationsByKey<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item) -> K,
+    K: Ord,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sort_pool();
+        self.combs.next()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.sort_pool();
+        self.combs.nth(n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.count()
+    }
+}
+
+impl<I, F, K> FusedIterator for CombinationsByKey<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item) -> K,
+    K: Ord,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations of an iterator,
+/// each one collected into a collection `C`.
+///
+/// See [`.combinations_set()`](crate::Itertools::combinations_set) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsCollect<I: Iterator, C> {
+    combs: Combinations<I>,
+    collection: PhantomData<C>,
+}
+
+impl<I, C> Clone for CombinationsCollect<I, C>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs, collection);
+}
+
+impl<I, C> fmt::Debug for CombinationsCollect<I, C>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsCollect, combs);
+}
+
+/// Create a new `CombinationsCollect` from a clonable iterator.
+pub fn combinations_collect<I, C>(iter: I, k: usize) -> CombinationsCollect<I, C>
+where
+    I: Iterator,
+    I::Item: Clone,
+    C: FromIterator<I::Item>,
+{
+    CombinationsCollect {
+        combs: combinations(iter, k),
+        collection: PhantomData,
+    }
+}
+
+impl<I, C> Iterator for CombinationsCollect<I, C>
+where
+    I: Iterator,
+    I::Item: Clone,
+    C: FromIterator<I::Item>,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.combs.advance() {
+            return None;
+        }
+        let pool = self.combs.src();
+        Some(
+            self.combs
+                .indices()
+                .iter()
+                .map(|i| pool[*i].clone())
+                .collect(),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.count()
+    }
+}
+
+impl<I, C> FusedIterator for CombinationsCollect<I, C>
+where
+    I: Iterator,
+    I::Item: Clone,
+    C: FromIterator<I::Item>,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations of an iterator,
+/// each one paired with its complement.
+///
+/// See [`.combinations_with_complement()`](crate::Itertools::combinations_with_complement)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsWithComplement<I: Iterator> {
+    combs: Combinations<I>,
+}
+
+impl<I> Clone for CombinationsWithComplement<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs);
+}
+
+impl<I> fmt::Debug for CombinationsWithComplement<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsWithComplement, combs);
+}
+
+/// Create a new `CombinationsWithComplement` from a clonable iterator.
+pub fn combinations_with_complement<I>(iter: I, k: usize) -> CombinationsWithComplement<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    CombinationsWithComplement {
+        combs: combinations(iter, k),
+    }
+}
+
+impl<I> Iterator for CombinationsWithComplement<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (Vec<I::Item>, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The complement needs the entire pool.
+        self.combs.prefill_pool(usize::MAX);
+        if self.combs.advance() {
+            return None;
+        }
+        let pool = self.combs.src();
+        let indices = self.combs.indices();
+        let mut chosen = indices.iter().peekable();
+        let mut complement = Vec::with_capacity(pool.len() - indices.len());
+        for i in 0..pool.len() {
+            if chosen.next_if_eq(&&i).is_none() {
+                complement.push(pool[i].clone());
+            }
+        }
+        Some((pool.get_at(indices), complement))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.count()
+    }
+}
+
+impl<I> FusedIterator for CombinationsWithComplement<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations of an iterator,
+/// each one paired with the previous one.
+///
+/// See [`.combinations_with_prev()`](crate::Itertools::combinations_with_prev) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsWithPrev<I: Iterator> {
+    combs: Combinations<I>,
+    prev: Option<Vec<I::Item>>,
+}
+
+impl<I> Clone for CombinationsWithPrev<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs, prev);
+}
+
+impl<I> fmt::Debug for CombinationsWithPrev<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsWithPrev, combs, prev);
+}
+
+/// Create a new `CombinationsWithPrev` from a clonable iterator.
+pub fn combinations_with_prev<I>(iter: I, k: usize) -> CombinationsWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    CombinationsWithPrev {
+        combs: combinations(iter, k),
+        prev: None,
+    }
+}
+
+impl<I> Iterator for CombinationsWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (Option<Vec<I::Item>>, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.combs.next()?;
+        let prev = self.prev.replace(current.clone());
+        Some((prev, current))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.count()
+    }
+}
+
+impl<I> FusedIterator for CombinationsWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator adaptor that iterates over the hashes of the `k`-length combinations of an iterator.
+///
+/// See [`.combinations_hash()`](crate::Itertools::combinations_hash) and
+/// [`.combinations_hash_with()`](crate::Itertools::combinations_hash_with)
+/// for more information.
+#[cfg(feature = "use_std")]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsHash<I: Iterator, S = BuildHasherDefault<DefaultHasher>> {
+    combs: Combinations<I>,
+    build_hasher: S,
+}
+
+#[cfg(feature = "use_std")]
+impl<I, S> Clone for CombinationsHash<I, S>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    S: Clone,
+{
+    clone_fields!(combs, build_hasher);
+}
+
+#[cfg(feature = "use_std")]
+impl<I, S> fmt::Debug for CombinationsHash<I, S>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsHash, combs);
+}
+
+/// Create a new `CombinationsHash` from an iterator, hashing with `DefaultHasher::new()`.
+#[cfg(feature = "use_std")]
+pub fn combinations_hash<I>(iter: I, k: usize) -> CombinationsHash<I>
+where
+    I: Iterator,
+    I::Item: Hash,
+{
+    combinations_hash_with(iter, k, BuildHasherDefault::default())
+}
+
+/// Create a new `CombinationsHash` from an iterator, hashing with the hashers built by
+/// `build_hasher`.
+#[cfg(feature = "use_std")]
+pub fn combinations_hash_with<I, S>(iter: I, k: usize, build_hasher: S) -> CombinationsHash<I, S>
+where
+    I: Iterator,
+    I::Item: Hash,
+    S: BuildHasher,
+{
+    CombinationsHash {
+        combs: combinations(iter, k),
+        build_hasher,
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl<I, S> Iterator for CombinationsHash<I, S>
+where
+    I: Iterator,
+    I::Item: Hash,
+    S: BuildHasher,
+{
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.combs.advance() {
+            return None;
+        }
+        let pool = self.combs.src();
+        let mut hasher = self.build_hasher.build_hasher();
+        hasher.write_usize(self.combs.k());
+        for i in self.combs.indices() {
+            pool[*i].hash(&mut hasher);
+        }
+        Some(hasher.finish())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            if self.combs.advance() {
+                return None;
+            }
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.remaining_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.n_and_count().1
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl<I, S> FusedIterator for CombinationsHash<I, S>
+where
+    I: Iterator,
+    I::Item: Hash,
+    S: BuildHasher,
+{
+}
+
+/// An iterator to iterate through every `stride`-th `k`-length combination in an iterator.
+///
+/// See [`.combinations_stride()`](crate::Itertools::combinations_stride) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsStride<I: Iterator> {
+    combs: Combinations<I>,
+    stride: usize,
+    /// The rank of the next combination to yield, `None` once done.
+    next_rank: Option<usize>,
+}
+
+impl<I> Clone for CombinationsStride<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs, stride, next_rank);
+}
+
+impl<I> fmt::Debug for CombinationsStride<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsStride, combs, stride, next_rank);
+}
+
+/// Create a new `CombinationsStride` from a clonable iterator.
+pub fn combinations_stride<I>(iter: I, k: usize, stride: usize) -> CombinationsStride<I>
+where
+    I: Iterator,
+{
+    assert!(stride != 0, "combinations_stride: stride must not be zero");
+    CombinationsStride {
+        combs: combinations(iter, k),
+        stride,
+        next_rank: Some(0),
+    }
+}
+
+impl<I: Iterator> CombinationsStride<I> {
+    /// The number of combinations left to yield for a pool of length `n`.
+    fn remaining_for(&self, n: usize) -> usize {
+        let rank = match self.next_rank {
+            Some(rank) => rank,
+            None => return 0,
+        };
+        // Ranks that do not fit in a `usize` are never reached.
+        let last_rank = match checked_binomial(n, self.combs.k()) {
+            Some(0) => return 0,
+            Some(count) => count - 1,
+            None => usize::MAX,
+        };
+        if last_rank < rank {
+            0
+        } else {
+            (last_rank - rank) / self.stride + 1
+        }
+    }
+}
+
+impl<I> Iterator for CombinationsStride<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rank = self.next_rank?;
+        // Unranking in lexicographic order needs the length of the pool.
+        self.combs.prefill_pool(usize::MAX);
+        if self.remaining_for(self.combs.n()) == 0 {
+            self.next_rank = None;
+            return None;
+        }
+        self.combs.jump_to(rank);
+        self.next_rank = rank.checked_add(self.stride);
+        Some(self.combs.src().get_at(self.combs.indices()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, upp) = self.combs.src().size_hint();
+        (
+            self.remaining_for(low),
+            upp.map(|upp| self.remaining_for(upp)),
+        )
+    }
+
+    fn count(mut self) -> usize {
+        self.combs.prefill_pool(usize::MAX);
+        self.remaining_for(self.combs.n())
+    }
+}
+
+impl<I> FusedIterator for CombinationsStride<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator to iterate through all the `k`-length combinations in an iterator,
+/// in co-lexicographic order.
+///
+/// See [`.combinations_colex()`](crate::Itertools::combinations_colex) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsColex<I: Iterator> {
+    indices: Vec<usize>,
+    pool: LazyBuffer<I>,
+    first: bool,
+}
+
+impl<I> Clone for CombinationsColex<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(indices, pool, first);
+}
+
+impl<I> fmt::Debug for CombinationsColex<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsColex, indices, pool, first);
+}
+
+/// Create a new `CombinationsColex` from a clonable iterator.
+pub fn combinations_colex<I>(iter: I, k: usize) -> CombinationsColex<I>
+where
+    I: Iterator,
+{
+    CombinationsColex {
+        indices: (0..k).collect(),
+        pool: LazyBuffer::new(iter),
+        first: true,
+    }
+}
+
+impl<I: Iterator> CombinationsColex<I> {
+    /// Initialises the iterator by filling a buffer with elements from the
+    /// iterator. Returns true if there are no combinations, false otherwise.
+    fn init(&mut self) -> bool {
+        let k = self.indices.len();
+        self.pool.prefill(k);
+        let done = k > self.pool.len();
+        if !done {
+            self.first = false;
+        }
+        done
+    }
+
+    /// Increments indices to the next combination in co-lexicographic order.
+    /// Returns true if we've run out of combinations, false otherwise.
+    ///
+    /// Unlike the lexicographic order, the next combination only needs one more element
+    /// from the source when the last index has to move.
+    fn increment_indices(&mut self) -> bool {
+        let k = self.indices.len();
+        for i in 0..k {
+            let upper = if i + 1 < k {
+                self.indices[i + 1]
+            } else {
+                if self.indices[i] + 1 == self.pool.len() {
+                    self.pool.get_next(); // may change pool size
+                }
+                self.pool.len()
+            };
+            if self.indices[i] + 1 < upper {
+                // Increment index, and reset the ones to its left.
+                self.indices[i] += 1;
+                for (j, index) in self.indices[..i].iter_mut().enumerate() {
+                    *index = j;
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The colex rank of the current combination, or None if it would overflow.
+    fn rank(&self) -> Option<usize> {
+        self.indices
+            .iter()
+            .enumerate()
+            .try_fold(0_usize, |rank, (i, &index)| {
+                rank.checked_add(checked_binomial(index, i + 1)?)
+            })
+    }
+
+    /// Jumps to the combination of the given colex rank.
+    /// Returns true if there is no such combination, then the pool is fully loaded.
+    fn jump_to(&mut self, mut rank: usize) -> bool {
+        let k = self.indices.len();
+        self.pool.prefill(k);
+        if k > self.pool.len() || (k == 0 && rank > 0) {
+            return true;
+        }
+        // The combinatorial number system: the index at position `i` is the greatest `c`
+        // such that `binomial(c, i + 1)` is not greater than what is left of the rank.
+        for i in (0..k).rev() {
+            let mut c = i;
+            while checked_binomial(c + 1, i + 1).map_or(false, |count| count <= rank) {
+                if i + 1 == k && c + 1 == self.pool.len() && !self.pool.get_next() {
+                    return true;
+                }
+                c += 1;
+            }
+            rank -= checked_binomial(c, i + 1).unwrap_or_default();
+            self.indices[i] = c;
+        }
+        self.first = false;
+        false
+    }
+
+    /// Moves to the last combination, so that there are no more combinations.
+    /// The pool should be fully loaded.
+    fn exhaust(&mut self) {
+        let n = self.pool.len();
+        let k = self.indices.len();
+        if k <= n {
+            for (i, index) in self.indices.iter_mut().enumerate() {
+                *index = n - k + i;
+            }
+            self.first = false;
+        }
+    }
+}
+
+impl<I> Iterator for CombinationsColex<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let done = if self.first {
+            self.init()
+        } else {
+            self.increment_indices()
+        };
+        if done {
+            return None;
+        }
+        Some(self.pool.get_at(&self.indices))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let rank = if self.first {
+            Some(n)
+        } else {
+            self.rank()
+                .and_then(|rank| rank.checked_add(1))
+                .and_then(|rank| rank.checked_add(n))
+        };
+        let rank = match rank {
+            Some(rank) => rank,
+            None => {
+                // Too many combinations for the rank to fit in a `usize`, so step one by one.
+                for _ in 0..n {
+                    self.next()?;
+                }
+                return self.next();
+            }
+        };
+        if self.jump_to(rank) {
+            self.exhaust();
+            return None;
+        }
+        Some(self.pool.get_at(&self.indices))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, upp) = self.pool.size_hint();
+        let remaining = |n| {
+            let k = self.indices.len();
+            if n < k {
+                return Some(0);
+            }
+            let count = checked_binomial(n, k)?;
+            if self.first {
+                Some(count)
+            } else {
+                Some(count - 1 - self.rank()?)
+            }
+        };
+        (
+            remaining(low).unwrap_or(usize::MAX),
+            upp.and_then(remaining),
+        )
+    }
+
+    fn count(self) -> usize {
+        let (first, rank) = (self.first, self.rank());
+        let k = self.indices.len();
+        let n = self.pool.count();
+        if n < k {
+            return 0;
+        }
+        let count = checked_binomial(n, k).expect("Iterator count would overflow");
+        if first {
+            count
+        } else {
+            // The rank is less than the count so it does not overflow either.
+            count - 1 - rank.unwrap()
+        }
+    }
+}
+
+impl<I> FusedIterator for CombinationsColex<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator to iterate through the `k`-length combinations of an iterator
+/// whose index prefixes are all feasible.
+///
+/// See [`.combinations_pruned()`](crate::Itertools::combinations_pruned) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsPruned<I: Iterator, F> {
+    /// The indices of the current combination, or of a feasible prefix while searching.
+    indices: Vec<usize>,
+    pool: LazyBuffer<I>,
+    k: usize,
+    first: bool,
+    done: bool,
+    feasible: F,
+}
+
+impl<I, F> Clone for CombinationsPruned<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(indices, pool, k, first, done, feasible);
+}
+
+impl<I, F> fmt::Debug for CombinationsPruned<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsPruned, indices, pool, k, first, done);
+}
+
+/// Create a new `CombinationsPruned` from a clonable iterator.
+pub fn combinations_pruned<I, F>(iter: I, k: usize, feasible: F) -> CombinationsPruned<I, F>
+where
+    I: Iterator,
+    F: FnMut(&[usize]) -> bool,
+{
+    CombinationsPruned {
+        indices: Vec::with_capacity(k),
+        pool: LazyBuffer::new(iter),
+        k,
+        first: true,
+        done: false,
+        feasible,
+    }
+}
+
+impl<I, F> CombinationsPruned<I, F>
+where
+    I: Iterator,
+    F: FnMut(&[usize]) -> bool,
+{
+    /// Searches, in lexicographic order, the first feasible combination whose index at
+    /// position `i` is at least `index`, the indices before `i` being kept.
+    ///
+    /// An infeasible prefix is never extended, its whole subtree is skipped.
+    /// Returns true if there is no such combination, false otherwise.
+    fn search(&mut self, mut i: usize, mut index: usize) -> bool {
+        loop {
+            // There must be room for the indices at positions `i..k`.
+            self.pool.prefill(index + self.k - i);
+            if index + self.k - i > self.pool.len() {
+                if i == 0 {
+                    return true;
+                }
+                // Backtrack: the prefix up to `i - 1` has no feasible extension.
+                i -= 1;
+                index = self.indices[i] + 1;
+                continue;
+            }
+            self.indices.truncate(i);
+            self.indices.push(index);
+            if !(self.feasible)(&self.indices) {
+                index += 1;
+            } else if i + 1 == self.k {
+                return false;
+            } else {
+                i += 1;
+                index += 1;
+            }
+        }
+    }
+}
+
+impl<I, F> Iterator for CombinationsPruned<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[usize]) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.done = if self.first {
+            self.first = false;
+            // The empty combination has no prefix to check.
+            self.k > 0 && self.search(0, 0)
+        } else {
+            self.k == 0 || self.search(self.k - 1, self.indices[self.k - 1] + 1)
+        };
+        if self.done {
+            return None;
+        }
+        Some(self.pool.get_at(&self.indices))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // Any remaining combination might be pruned.
+        let upp = self.pool.size_hint().1.and_then(|n| {
+            if self.first {
+                checked_binomial(n, self.k)
+            } else {
+                remaining_for(n, false, &self.indices)
+            }
+        });
+        (0, upp)
+    }
+}
+
+impl<I, F> FusedIterator for CombinationsPruned<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[usize]) -> bool,
+{
+}
+
+/// Writes in `indices` the `k`-combination of `n` elements of the given `rank` in
+/// lexicographic order, where `k` is the length of `indices`.
+///
+/// `rank` should be less than `binomial(n, k)`.
+pub(crate) fn unrank(n: usize, mut rank: usize, indices: &mut [usize]) {
+    let k = indices.len();
+    debug_assert!(checked_binomial(n, k).map_or(true, |count| rank < count));
+    let mut start = 0;
+    for (i, elt) in indices.iter_mut().enumerate() {
+        let mut index = start;
+        // There are `binomial(n - 1 - index, k - 1 - i)` combinations with `index` at position `i`.
+        while let Some(count) = checked_binomial(n - 1 - index, k - 1 - i) {
+            if rank < count {
+                break;
+            }
+            rank -= count;
+            index += 1;
+        }
+        *elt = index;
+        start = index + 1;
+    }
+}
+
+/// Return all the `k`-combinations of the indices `0..n`, in lexicographic order.
+///
+/// This is the index plan of [`.combinations(k)`](crate::Itertools::combinations) over
+/// any `n` elements: it can be computed once and then applied to many data slices of
+/// length `n`. No elements are cloned in the process.
+///
+/// ```
+/// let plan = itertools::index_plan(2, 4);
+/// assert_eq!(plan, vec![
+///     vec![0, 1],
+///     vec![0, 2],
+///     vec![0, 3],
+///     vec![1, 2],
+///     vec![1, 3],
+///     vec![2, 3],
+/// ]);
+///
+/// for data in [["a", "b", "c", "d"], ["w", "x", "y", "z"]] {
+///     let pairs: Vec<_> = plan.iter().map(|p| (data[p[0]], data[p[1]])).collect();
+///     assert_eq!(pairs[3], (data[1], data[2]));
+/// }
+/// ```
+pub fn index_plan(k: usize, n: usize) -> Vec<Vec<usize>> {
+    // A pool of zero-sized elements: only the indices matter.
+    let mut combs = combinations(std::iter::repeat(()).take(n), k);
+    let mut plan = Vec::with_capacity(checked_binomial(n, k).unwrap_or(0));
+    while !combs.advance() {
+        plan.push(combs.indices().to_vec());
+    }
+    plan
+}
+
+/// For a given size `n`, return the count of remaining combinations or None if it would overflow.
+fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
+    let k = indices.len();
+    if n < k {
+        Some(0)
+    } else if first {
+        checked_binomial(n, k)
+    } else {
+        // https://en.wikipedia.org/wiki/Combinatorial_number_system
+        // http://www.site.uottawa.ca/~lucia/courses/5165-09/GenCombObj.pdf
+
+        // The combinations generated after the current one can be counted by counting as follows:
+        // - The subsequent combinations that differ in indices[0]:
+        //   If subsequent combinations differ in indices[0], then their value for indices[0]
+        //   must be at least 1 greater than the current indices[0].
+        //   As indices is strictly monotonically sorted, this means we can effectively choose k values
+        //   from (n - 1 - indices[0]), leading to binomial(n - 1 - indices[0], k) possibilities.
+        // - The subsequent combinations with same indices[0], but differing indices[1]:
+        //   Here we can choose k - 1 values from (n - 1 - indices[1]) values,
+        //   leading to binomial(n - 1 - indices[1], k - 1) possibilities.
+        // - (...)
+        // - The subsequent combinations with same indices[0..=i], but differing indices[i]:
+        //   Here we can choose k - i values from (n - 1 - indices[i]) values: binomial(n - 1 - indices[i], k - i).
+        //   Since subsequent combinations can in any index, we must sum up the aforementioned binomial coefficients.
+
+        // Below, `n0` resembles indices[i].
+        indices.iter().enumerate().try_fold(0usize, |sum, (i, n0)| {
+            sum.checked_add(checked_binomial(n - 1 - *n0, k - i)?)
+        })
+    }
+}
+
+#[test]
+fn test_reset_to_zero() {
+    // Whatever the state of the combinations, `reset(0)` yields one empty combination.
+    for n in 0..4 {
+        for k in 0..6 {
+            let fresh = combinations(0..n, k);
+            let mut started = fresh.clone();
+            started.next();
+            let mut peeked = fresh.clone();
+            peeked.peek();
+            let mut exhausted = fresh.clone();
+            exhausted.by_ref().for_each(drop);
+            let mut exhausted_twice = exhausted.clone();
+            exhausted_twice.reset(0);
+            exhausted_twice.by_ref().for_each(drop);
+            for mut it in [fresh, started, peeked, exhausted, exhausted_twice] {
+                it.reset(0);
+                assert_eq!(it.k(), 0);
+                assert_eq!(it.size_hint().0, 1);
+                assert_eq!(it.clone().count(), 1);
+                assert_eq!(it.clone().nth(0), Some(Vec::new()));
+                assert_eq!(it.clone().nth(1), None);
+                assert_eq!(it.peek(), Some(&[][..]));
+                assert_eq!(it.next(), Some(Vec::new()));
+                assert_eq!(it.size_hint(), (0, Some(0)));
+                assert_eq!(it.next(), None);
+                assert_eq!(it.peek(), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_random_resets() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..500 {
+        let n = rng.gen_range(0, 7);
+        let k = rng.gen_range(0, 9);
+        // The source length is not always known.
+        let mut it = combinations((0..n).filter(|_| true), k);
+        let mut expected: alloc::collections::VecDeque<_> = combinations(0..n, k).collect();
+        for _ in 0..30 {
+            match rng.gen_range(0, 4) {
+                0 => assert_eq!(it.next(), expected.pop_front()),
+                1 => {
+                    let m = rng.gen_range(0, 4);
+                    let nth = expected.drain(..expected.len().min(m)).count();
+                    let elt = if nth == m { expected.pop_front() } else { None };
+                    assert_eq!(it.nth(m), elt);
+                }
+                2 => assert_eq!(it.peek(), expected.front().map(|c| &c[..])),
+                _ => {
+                    let k = rng.gen_range(0, 9);
+                    it.reset(k);
+                    expected = combinations(0..n, k).collect();
+                }
+            }
+            assert_eq!(it.clone().count(), expected.len());
+        }
+    }
+}
+
+#[test]
+fn test_reset_max_k() {
+    // `reset` clamps to the cap, without pulling more from an infinite source.
+    let mut it = combinations(0.., 1).with_max_k(2);
+    assert_eq!(it.next(), Some(vec![0]));
+    it.reset(usize::MAX);
+    assert_eq!(it.k(), 2);
+    assert_eq!(it.n(), 2);
+    assert_eq!(it.next(), Some(vec![0, 1]));
+    it.reset(0);
+    assert_eq!(it.next(), Some(vec![]));
+    assert_eq!(it.next(), None);
+    it.reset(5);
+    assert_eq!(it.k(), 2);
+    assert_eq!(it.nth(2), Some(vec![0, 3]));
+}
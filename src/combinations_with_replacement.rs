@@ -10,7 +10,6 @@ use crate::adaptors::checked_binomial;
 ///
 /// See [`.combinations_with_replacement()`](crate::Itertools::combinations_with_replacement)
 /// for more information.
-#[derive(Clone)]
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 pub struct CombinationsWithReplacement<I>
 where
@@ -22,6 +21,25 @@ where
     first: bool,
 }
 
+impl<I> Clone for CombinationsWithReplacement<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(indices, pool, first);
+
+    /// Reuses the allocations of `self`.
+    fn clone_from(&mut self, source: &Self) {
+        if self.indices.len() == source.indices.len() {
+            self.indices.copy_from_slice(&source.indices);
+        } else {
+            self.indices.clone_from(&source.indices);
+        }
+        self.pool.clone_from(&source.pool);
+        self.first = source.first;
+    }
+}
+
 impl<I> fmt::Debug for CombinationsWithReplacement<I>
 where
     I: Iterator + fmt::Debug,
@@ -46,16 +64,82 @@ where
     }
 }
 
+impl<T> CombinationsWithReplacement<alloc::vec::IntoIter<T>>
+where
+    T: Clone,
+{
+    /// Create a new `CombinationsWithReplacement` of length `k` over the elements of an
+    /// already buffered `pool`.
+    ///
+    /// The elements are used directly as the pool, without going through a source iterator,
+    /// so size hints are exact from the start.
+    ///
+    /// ```
+    /// use itertools::CombinationsWithReplacement;
+    ///
+    /// let it = CombinationsWithReplacement::from_pool(vec!['a', 'b'], 2);
+    /// assert_eq!(it.size_hint(), (3, Some(3)));
+    /// itertools::assert_equal(it, vec![vec!['a', 'a'], vec!['a', 'b'], vec!['b', 'b']]);
+    /// ```
+    pub fn from_pool(pool: Vec<T>, k: usize) -> Self {
+        Self {
+            indices: alloc::vec![0; k].into_boxed_slice(),
+            pool: LazyBuffer::from_vec(pool),
+            first: true,
+        }
+    }
+}
+
 impl<I> CombinationsWithReplacement<I>
 where
     I: Iterator,
     I::Item: Clone,
 {
+    /// Collects the remaining combinations into a `Vec`, allocated once.
+    ///
+    /// The source is buffered first so that the number of combinations is known, even when
+    /// the size hint of the source is not exact. If that number overflows a `usize`,
+    /// the outer `Vec` grows as with [`collect`](Iterator::collect).
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let combs = (1..6).filter(|x| x % 2 == 1).combinations_with_replacement(2).into_vec();
+    /// assert_eq!(combs.len(), 6);
+    /// assert_eq!(combs[..3], [vec![1, 1], vec![1, 3], vec![1, 5]]);
+    /// ```
+    pub fn into_vec(mut self) -> Vec<Vec<I::Item>> {
+        self.pool.prefill(usize::MAX);
+        let mut combs = match self.size_hint() {
+            (low, Some(upp)) if low == upp => Vec::with_capacity(low),
+            _ => Vec::new(),
+        };
+        combs.extend(self);
+        combs
+    }
+
+    /// Advances to the first combination, or to the next one.
+    ///
+    /// Returns the first index changed since the previous combination (`0` for the first one),
+    /// or `None` if we've run out of combinations.
+    fn advance(&mut self) -> Option<usize> {
+        if self.first {
+            // In empty edge cases, stop iterating immediately
+            if !(self.indices.is_empty() || self.pool.len() > 0 || self.pool.get_next()) {
+                return None;
+            }
+            self.first = false;
+            Some(0)
+        } else {
+            self.increment_indices()
+        }
+    }
+
     /// Increments indices representing the combination to advance to the next
     /// (in lexicographic order by increasing sequence) combination.
     ///
-    /// Returns true if we've run out of combinations, false otherwise.
-    fn increment_indices(&mut self) -> bool {
+    /// Returns the first changed index, or `None` if we've run out of combinations.
+    fn increment_indices(&mut self) -> Option<usize> {
         // Check if we need to consume more from the iterator
         // This will run while we increment our first index digit
         self.pool.get_next();
@@ -74,10 +158,10 @@ where
                 // We need to update the rightmost non-max value
                 // and all those to the right
                 self.indices[increment_from..].fill(increment_value);
-                false
+                Some(increment_from)
             }
             // Otherwise, we're done
-            None => true,
+            None => None,
         }
     }
 }
@@ -90,32 +174,14 @@ where
     type Item = Vec<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.first {
-            // In empty edge cases, stop iterating immediately
-            if !(self.indices.is_empty() || self.pool.get_next()) {
-                return None;
-            }
-            self.first = false;
-        } else if self.increment_indices() {
-            return None;
-        }
+        self.advance()?;
         Some(self.pool.get_at(&self.indices))
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        if self.first {
-            // In empty edge cases, stop iterating immediately
-            if !(self.indices.is_empty() || self.pool.get_next()) {
-                return None;
-            }
-            self.first = false;
-        } else if self.increment_indices() {
-            return None;
-        }
+        self.advance()?;
         for _ in 0..n {
-            if self.increment_indices() {
-                return None;
-            }
+            self.increment_indices()?;
         }
         Some(self.pool.get_at(&self.indices))
     }
@@ -145,6 +211,115 @@ where
 {
 }
 
+/// An iterator to iterate through all the `k`-length combinations with replacement of an
+/// iterator, each one paired with the previous one.
+///
+/// See [`.combinations_with_replacement_with_prev()`](crate::Itertools::combinations_with_replacement_with_prev)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsWithReplacementWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    combs: CombinationsWithReplacement<I>,
+    prev: Option<Vec<I::Item>>,
+    changed_from: Option<usize>,
+}
+
+impl<I> Clone for CombinationsWithReplacementWithPrev<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(combs, prev, changed_from);
+}
+
+impl<I> fmt::Debug for CombinationsWithReplacementWithPrev<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug + Clone,
+{
+    debug_fmt_fields!(
+        CombinationsWithReplacementWithPrev,
+        combs,
+        prev,
+        changed_from
+    );
+}
+
+/// Create a new `CombinationsWithReplacementWithPrev` from a clonable iterator.
+pub fn combinations_with_replacement_with_prev<I>(
+    iter: I,
+    k: usize,
+) -> CombinationsWithReplacementWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    CombinationsWithReplacementWithPrev {
+        combs: combinations_with_replacement(iter, k),
+        prev: None,
+        changed_from: None,
+    }
+}
+
+impl<I> CombinationsWithReplacementWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    /// Returns the first position where the last yielded combination differs from the
+    /// previous one, or `None` if no combination was yielded yet.
+    ///
+    /// All the elements from that position onward changed (to the same element),
+    /// and it is `0` for the first combination.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (1..4).combinations_with_replacement_with_prev(3);
+    /// assert_eq!(it.changed_from(), None);
+    /// it.next();
+    /// assert_eq!(it.changed_from(), Some(0));
+    /// assert_eq!(it.nth(2), Some((Some(vec![1, 1, 3]), vec![1, 2, 2])));
+    /// assert_eq!(it.changed_from(), Some(1));
+    /// ```
+    pub fn changed_from(&self) -> Option<usize> {
+        self.changed_from
+    }
+}
+
+impl<I> Iterator for CombinationsWithReplacementWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (Option<Vec<I::Item>>, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.changed_from = Some(self.combs.advance()?);
+        let current = self.combs.pool.get_at(&self.combs.indices);
+        let prev = self.prev.replace(current.clone());
+        Some((prev, current))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.combs.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.combs.count()
+    }
+}
+
+impl<I> FusedIterator for CombinationsWithReplacementWithPrev<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
 /// For a given size `n`, return the count of remaining combinations with replacement or None if it would overflow.
 fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
     // With a "stars and bars" representation, choose k values with replacement from n values is
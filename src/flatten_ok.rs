@@ -110,7 +110,7 @@ where
             _ => (0, None),
         };
 
-        size_hint::add(size_hint::add(inner_front, inner_back), outer)
+        size_hint::saturating_add(size_hint::saturating_add(inner_front, inner_back), outer)
     }
 }
 
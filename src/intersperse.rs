@@ -103,7 +103,7 @@ where
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let mut sh = self.iter.size_hint();
-        sh = size_hint::add(sh, sh);
+        sh = size_hint::saturating_add(sh, sh);
         match self.peek {
             Some(Some(_)) => size_hint::add_scalar(sh, 1),
             Some(None) => sh,
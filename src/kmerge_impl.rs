@@ -225,7 +225,7 @@ where
         self.heap
             .iter()
             .map(|i| i.size_hint())
-            .reduce(size_hint::add)
+            .reduce(size_hint::saturating_add)
             .unwrap_or((0, Some(0)))
     }
 }
@@ -4,12 +4,25 @@ use std::ops::Index;
 
 use crate::size_hint::{self, SizeHint};
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct LazyBuffer<I: Iterator> {
     it: Fuse<I>,
     buffer: Vec<I::Item>,
 }
 
+impl<I> Clone for LazyBuffer<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(it, buffer);
+
+    fn clone_from(&mut self, source: &Self) {
+        self.it.clone_from(&source.it);
+        self.buffer.clone_from(&source.buffer);
+    }
+}
+
 impl<I> LazyBuffer<I>
 where
     I: Iterator,
@@ -42,6 +55,14 @@ where
         }
     }
 
+    pub fn sort_by_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: FnMut(&I::Item) -> K,
+    {
+        self.buffer.sort_by_key(f);
+    }
+
     pub fn prefill(&mut self, len: usize) {
         let buffer_len = self.buffer.len();
         if len > buffer_len {
@@ -51,6 +72,16 @@ where
     }
 }
 
+impl<T> LazyBuffer<alloc::vec::IntoIter<T>> {
+    /// Creates a fully buffered `LazyBuffer` from its elements.
+    pub fn from_vec(buffer: Vec<T>) -> Self {
+        Self {
+            it: Vec::new().into_iter().fuse(),
+            buffer,
+        }
+    }
+}
+
 impl<I> LazyBuffer<I>
 where
     I: Iterator,
@@ -61,6 +92,16 @@ where
     }
 }
 
+impl<I> LazyBuffer<I>
+where
+    I: Iterator,
+    I::Item: Copy,
+{
+    pub fn get_at_copied(&self, indices: &[usize]) -> Vec<I::Item> {
+        indices.iter().map(|i| self.buffer[*i]).collect()
+    }
+}
+
 impl<I, J> Index<J> for LazyBuffer<I>
 where
     I: Iterator,
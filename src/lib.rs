@@ -86,17 +86,30 @@ pub use std::iter as __std_iter;
 
 /// The concrete iterator types.
 pub mod structs {
-    #[cfg(feature = "use_alloc")]
-    pub use crate::adaptors::MultiProduct;
     pub use crate::adaptors::{
         Batching, Coalesce, Dedup, DedupBy, DedupByWithCount, DedupWithCount, FilterMapOk,
         FilterOk, Interleave, InterleaveShortest, MapInto, MapOk, Positions, Product, PutBack,
         TakeWhileRef, TupleCombinations, Update, WhileSome,
     };
     #[cfg(feature = "use_alloc")]
-    pub use crate::combinations::Combinations;
+    pub use crate::adaptors::{
+        MultiProduct, MultiProductAxis, MultiProductBuilder, MultiProductFactories,
+        MultiProductFill, MultiProductRevOrder, MultiProductWeighted,
+    };
+    #[cfg(feature = "use_std")]
+    pub use crate::combinations::CombinationsHash;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::combinations::{
+        Combinations, CombinationsByKey, CombinationsColex, CombinationsCollect,
+        CombinationsCopied, CombinationsDedupBy, CombinationsFirstGroup, CombinationsFlagged,
+        CombinationsGroupByFirst, CombinationsMapWhile, CombinationsOnPoolGrow,
+        CombinationsPollNew, CombinationsPruned, CombinationsStride, CombinationsWithComplement,
+        CombinationsWithPrev,
+    };
     #[cfg(feature = "use_alloc")]
-    pub use crate::combinations_with_replacement::CombinationsWithReplacement;
+    pub use crate::combinations_with_replacement::{
+        CombinationsWithReplacement, CombinationsWithReplacementWithPrev,
+    };
     pub use crate::cons_tuples_impl::ConsTuples;
     #[cfg(feature = "use_std")]
     pub use crate::duplicates_impl::{Duplicates, DuplicatesBy};
@@ -123,7 +136,7 @@ pub mod structs {
     #[cfg(feature = "use_alloc")]
     pub use crate::permutations::Permutations;
     #[cfg(feature = "use_alloc")]
-    pub use crate::powerset::Powerset;
+    pub use crate::powerset::{Powerset, PowersetIndexed, PowersetMapIndexed, PowersetMasked};
     pub use crate::process_results_impl::ProcessResults;
     #[cfg(feature = "use_alloc")]
     pub use crate::put_back_n_impl::PutBackN;
@@ -150,6 +163,10 @@ pub mod traits {
     pub use crate::tuple_impl::HomogeneousTuple;
 }
 
+#[cfg(feature = "use_alloc")]
+pub use crate::adaptors::{multi_cartesian_product_factories, product_rank, product_unrank};
+#[cfg(feature = "use_alloc")]
+pub use crate::combinations::index_plan;
 pub use crate::concat_impl::concat;
 pub use crate::cons_tuples_impl::cons_tuples;
 pub use crate::diff::diff_with;
@@ -1252,6 +1269,142 @@ pub trait Itertools: Iterator {
         adaptors::multi_cartesian_product(self)
     }
 
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// all subiterators returned by meta-iterator `self`, which yields `axes` subiterators.
+    ///
+    /// This is [`.multi_cartesian_product()`](Itertools::multi_cartesian_product), but the
+    /// subiterators are stored in a vector allocated once with room for `axes` of them,
+    /// even when the size hint of `self` does not tell how many there are, e.g. after a
+    /// `filter`. A wrong `axes` is only a missed optimization.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..4)
+    ///     .filter(|i| i % 2 == 0)
+    ///     .map(|i| i..(i + 2))
+    ///     .multi_cartesian_product_with_axes(2);
+    /// itertools::assert_equal(it, vec![vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3]]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn multi_cartesian_product_with_axes(
+        self,
+        axes: usize,
+    ) -> MultiProduct<<Self::Item as IntoIterator>::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+        <Self::Item as IntoIterator>::IntoIter: Clone,
+        <Self::Item as IntoIterator>::Item: Clone,
+    {
+        adaptors::multi_cartesian_product_with_axes(self, axes)
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// all subiterators returned by meta-iterator `self`, each product being
+    /// turned into a vector by the closure `f`.
+    ///
+    /// `f` receives each product as a slice and pushes its output into a buffer,
+    /// which is cleared beforehand and reused for every product.
+    /// The iterator element type is `Vec<R>`: each element is a clone of the buffer.
+    /// To avoid these clones, consume the iterator with
+    /// [`for_each_filled`](crate::structs::MultiProductFill::for_each_filled) instead,
+    /// which lends the buffer.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..2)
+    ///     .map(|i| (i * 2)..(i * 2 + 2))
+    ///     .multi_cartesian_product_fill(|p, buf| buf.extend(p.iter().rev().copied()));
+    /// itertools::assert_equal(it, vec![vec![2, 0], vec![3, 0], vec![2, 1], vec![3, 1]]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn multi_cartesian_product_fill<F, R>(
+        self,
+        f: F,
+    ) -> MultiProductFill<<Self::Item as IntoIterator>::IntoIter, F, R>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+        <Self::Item as IntoIterator>::IntoIter: Clone,
+        <Self::Item as IntoIterator>::Item: Clone,
+        F: FnMut(&[<Self::Item as IntoIterator>::Item], &mut Vec<R>),
+    {
+        adaptors::multi_cartesian_product_fill(self, f)
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// all subiterators returned by meta-iterator `self`, the first subiterator
+    /// varying the fastest.
+    ///
+    /// The products are the same as with
+    /// [`.multi_cartesian_product()`](Itertools::multi_cartesian_product), in a different
+    /// order: the elements of each product still follow the order of the subiterators,
+    /// but the first subiterator varies the fastest instead of the last one
+    /// (column-major order instead of row-major order).
+    /// This is not the same as iterating backwards.
+    ///
+    /// The subiterators are collected when this adaptor is created.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![0..2, 10..13].into_iter().multi_cartesian_product_rev_order();
+    /// itertools::assert_equal(it, vec![
+    ///     vec![0, 10], vec![1, 10],
+    ///     vec![0, 11], vec![1, 11],
+    ///     vec![0, 12], vec![1, 12],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn multi_cartesian_product_rev_order(
+        self,
+    ) -> MultiProductRevOrder<<Self::Item as IntoIterator>::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+        <Self::Item as IntoIterator>::IntoIter: Clone,
+        <Self::Item as IntoIterator>::Item: Clone,
+    {
+        adaptors::multi_cartesian_product_rev_order(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// all subiterators of weighted elements returned by meta-iterator `self`.
+    ///
+    /// Each subiterator yields `(element, weight)` pairs, and the iterator element type is
+    /// `(Vec<T>, f64)`: the elements of each product, in the same order as
+    /// [`.multi_cartesian_product()`](Itertools::multi_cartesian_product), along with the
+    /// product of their weights (`1.0` for the nullary product).
+    ///
+    /// Weights are only multiplied: zero, negative or NaN weights are not filtered out.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let axes = vec![vec![('a', 0.5), ('b', 0.5)], vec![('x', 0.25), ('y', 0.75)]];
+    /// let it = axes.into_iter().multi_cartesian_product_weighted();
+    /// itertools::assert_equal(it, vec![
+    ///     (vec!['a', 'x'], 0.125),
+    ///     (vec!['a', 'y'], 0.375),
+    ///     (vec!['b', 'x'], 0.125),
+    ///     (vec!['b', 'y'], 0.375),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn multi_cartesian_product_weighted<T>(
+        self,
+    ) -> MultiProductWeighted<<Self::Item as IntoIterator>::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator<Item = (T, f64)>,
+        <Self::Item as IntoIterator>::IntoIter: Clone,
+        T: Clone,
+    {
+        adaptors::multi_cartesian_product_weighted(self)
+    }
+
     /// Return an iterator adaptor that uses the passed-in closure to
     /// optionally merge together consecutive elements.
     ///
@@ -1709,6 +1862,510 @@ pub trait Itertools: Iterator {
         combinations::combinations(self, k)
     }
 
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, each one collected into a `BTreeSet`.
+    ///
+    /// This is like [`.combinations(k)`](Itertools::combinations) but the elements are
+    /// collected directly into a set, without an intermediate `Vec`.
+    /// Equal elements of a combination are deduplicated by the set.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let it = vec![3, 1, 1].into_iter().combinations_set(2);
+    /// itertools::assert_equal(it, vec![
+    ///     BTreeSet::from([1, 3]),
+    ///     BTreeSet::from([1, 3]),
+    ///     BTreeSet::from([1]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_set(
+        self,
+        k: usize,
+    ) -> CombinationsCollect<Self, alloc::collections::BTreeSet<Self::Item>>
+    where
+        Self: Sized,
+        Self::Item: Clone + Ord,
+    {
+        combinations::combinations_collect(self, k)
+    }
+
+    /// Collect all the `k`-length combinations of the elements from an iterator into a
+    /// single flat `Vec`, one combination after the other, and return it with the row width `k`.
+    ///
+    /// The combinations are in the same order as [`.combinations(k)`](Itertools::combinations)
+    /// and form the rows of a row-major matrix, without one allocation per row.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (matrix, width) = (0..4).combinations_into_matrix(2);
+    /// assert_eq!(width, 2);
+    /// assert_eq!(matrix, [0, 1, 0, 2, 0, 3, 1, 2, 1, 3, 2, 3]);
+    /// assert_eq!(matrix.chunks(width).nth(3), Some(&[1, 2][..]));
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_into_matrix(self, k: usize) -> (Vec<Self::Item>, usize)
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        let mut combs = self.combinations(k);
+        let mut matrix = Vec::with_capacity(combs.size_hint().0.saturating_mul(k));
+        let mut row = Vec::with_capacity(k);
+        while combs.next_into(&mut row) {
+            matrix.append(&mut row);
+        }
+        (matrix, k)
+    }
+
+    /// Return the `k`-length combination of the elements from an iterator with the
+    /// maximum key, or `None` if there are no such combinations.
+    ///
+    /// This is like `.combinations(k).max_by_key(key)`, with `key` taking a slice,
+    /// but without allocating a new `Vec` for each combination: only two buffers are used.
+    /// Like [`Iterator::max_by_key`], the last maximum combination is returned.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let xs = [3, -1, 4, -1, 5];
+    /// let best = xs.iter().combinations_max_by_key(2, |c| c[0] * c[1]);
+    /// assert_eq!(best, Some(vec![&4, &5]));
+    /// assert_eq!(xs.iter().combinations_max_by_key(6, |c| c.len()), None);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_max_by_key<K, F>(self, k: usize, mut key: F) -> Option<Vec<Self::Item>>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        K: Ord,
+        F: FnMut(&[Self::Item]) -> K,
+    {
+        let mut combs = self.combinations(k);
+        let mut best = Vec::with_capacity(k);
+        if !combs.next_into(&mut best) {
+            return None;
+        }
+        let mut best_key = key(&best);
+        let mut row = Vec::with_capacity(k);
+        while combs.next_into(&mut row) {
+            let row_key = key(&row);
+            if row_key >= best_key {
+                best_key = row_key;
+                std::mem::swap(&mut best, &mut row);
+            }
+        }
+        Some(best)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, each one collected into a `HashSet`.
+    ///
+    /// This is like [`.combinations(k)`](Itertools::combinations) but the elements are
+    /// collected directly into a set, without an intermediate `Vec`.
+    /// Equal elements of a combination are deduplicated by the set.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use std::collections::HashSet;
+    ///
+    /// let it = (1..4).combinations_hash_set(2);
+    /// itertools::assert_equal(it, vec![
+    ///     HashSet::from([1, 2]),
+    ///     HashSet::from([1, 3]),
+    ///     HashSet::from([2, 3]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn combinations_hash_set(self, k: usize) -> CombinationsCollect<Self, HashSet<Self::Item>>
+    where
+        Self: Sized,
+        Self::Item: Clone + Eq + Hash,
+    {
+        combinations::combinations_collect(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the hashes of the `k`-length
+    /// combinations of the elements from an iterator.
+    ///
+    /// Iterator element type is `u64`. Combinations are in the same order as
+    /// [`.combinations(k)`](Itertools::combinations), but each one is hashed with
+    /// a [`DefaultHasher`](std::collections::hash_map::DefaultHasher) created with
+    /// `DefaultHasher::new()` instead of being collected, so no `Vec` is allocated per combination.
+    ///
+    /// Equal combinations have equal hashes, but the hashes are only stable within one
+    /// compiler version: the algorithm of `DefaultHasher` is not guaranteed across releases
+    /// of Rust. To compare hashes across runs built with different toolchains, pick a hasher
+    /// with [`.combinations_hash_with()`](Itertools::combinations_hash_with).
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // The combinations are `[1, 2]`, `[1, 1]` and `[2, 1]`.
+    /// let hashes = [1, 2, 1].iter().combinations_hash(2).collect_vec();
+    /// assert_eq!(hashes.len(), 3);
+    /// assert_eq!(hashes[0], [1, 2, 3].iter().combinations_hash(2).next().unwrap());
+    /// assert_ne!(hashes[0], hashes[2]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn combinations_hash(self, k: usize) -> CombinationsHash<Self>
+    where
+        Self: Sized,
+        Self::Item: Hash,
+    {
+        combinations::combinations_hash(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the hashes of the `k`-length
+    /// combinations of the elements from an iterator, each one hashed with a hasher
+    /// built by `build_hasher`.
+    ///
+    /// Iterator element type is `u64`. This is like
+    /// [`.combinations_hash(k)`](Itertools::combinations_hash), but with a fixed hasher
+    /// the hashes are as stable as that hasher, e.g. to deduplicate combinations across runs.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use std::hash::{BuildHasherDefault, Hasher};
+    ///
+    /// // A hasher whose algorithm is fixed: the sum of the written bytes.
+    /// #[derive(Default)]
+    /// struct ByteSum(u64);
+    ///
+    /// impl Hasher for ByteSum {
+    ///     fn finish(&self) -> u64 {
+    ///         self.0
+    ///     }
+    ///     fn write(&mut self, bytes: &[u8]) {
+    ///         self.0 += bytes.iter().map(|&b| u64::from(b)).sum::<u64>();
+    ///     }
+    /// }
+    ///
+    /// let build_hasher = BuildHasherDefault::<ByteSum>::default();
+    /// let hashes = [1_u8, 2, 4].iter().combinations_hash_with(2, build_hasher).collect_vec();
+    /// // The length `2` is hashed first, then the elements.
+    /// assert_eq!(hashes, vec![5, 7, 8]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn combinations_hash_with<S>(self, k: usize, build_hasher: S) -> CombinationsHash<Self, S>
+    where
+        Self: Sized,
+        Self::Item: Hash,
+        S: std::hash::BuildHasher,
+    {
+        combinations::combinations_hash_with(self, k, build_hasher)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, each one paired with its complement.
+    ///
+    /// Iterator element type is `(Vec<Self::Item>, Vec<Self::Item>)`: the chosen elements
+    /// and the `n - k` other elements, both in their original order.
+    /// Combinations are in the same order as [`.combinations(k)`](Itertools::combinations).
+    ///
+    /// Note: This forces the full buffering of the source iterator on the first call to `next`,
+    /// so it should not be used with an infinite iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..4).combinations_with_complement(1);
+    /// itertools::assert_equal(it, vec![
+    ///     (vec![1], vec![2, 3]),
+    ///     (vec![2], vec![1, 3]),
+    ///     (vec![3], vec![1, 2]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_with_complement(self, k: usize) -> CombinationsWithComplement<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations::combinations_with_complement(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over every `stride`-th `k`-length combination
+    /// of the elements from an iterator.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. The combinations are the ones of ranks
+    /// `0`, `stride`, `2 * stride`, ... of [`.combinations(k)`](Itertools::combinations),
+    /// like `.combinations(k).step_by(stride)`, but each one is computed directly from its
+    /// rank instead of generating the ones in between.
+    ///
+    /// Note: The whole iterator is buffered to get the first combination.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..5).combinations_stride(2, 2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![1, 2],
+    ///     vec![1, 4],
+    ///     vec![2, 4],
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    #[cfg(feature = "use_alloc")]
+    fn combinations_stride(self, k: usize, stride: usize) -> CombinationsStride<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations::combinations_stride(self, k, stride)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, in co-lexicographic order.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. The combinations are the same as
+    /// [`.combinations(k)`](Itertools::combinations) but ordered by their last element first,
+    /// then by the one before it, and so on. The indices of each combination are increasing.
+    ///
+    /// This order does not depend on the length of the iterator, so elements are only
+    /// taken from it when a combination needs them. Because the rank of a combination
+    /// is easy to compute in this order, `nth` and `count` do not step through combinations.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..5).combinations_colex(2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![1, 2],
+    ///     vec![1, 3],
+    ///     vec![2, 3],
+    ///     vec![1, 4],
+    ///     vec![2, 4],
+    ///     vec![3, 4],
+    /// ]);
+    ///
+    /// // Works with infinite iterators.
+    /// assert_eq!((0..).combinations_colex(3).nth(1000), Some(vec![3, 8, 19]));
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_colex(self, k: usize) -> CombinationsColex<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations::combinations_colex(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, skipping the ones with an infeasible index prefix.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. Combinations are in the same order as
+    /// [`.combinations(k)`](Itertools::combinations).
+    ///
+    /// `feasible` is called on the pool indices of the prefixes of combinations, shortest
+    /// first, and must be monotone: if a prefix is infeasible, all of its extensions are
+    /// assumed infeasible too. Such a prefix is never extended, so whole subtrees of
+    /// combinations are skipped without being generated, unlike with `.filter`.
+    /// The combinations yielded are the ones whose prefixes are all feasible.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // Combinations of weights whose sum does not exceed 6.
+    /// let weights = [1, 2, 3, 4, 5];
+    /// let it = weights
+    ///     .iter()
+    ///     .combinations_pruned(3, |indices| indices.iter().map(|&i| weights[i]).sum::<i32>() <= 6);
+    /// itertools::assert_equal(it, vec![vec![&1, &2, &3]]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_pruned<F>(self, k: usize, feasible: F) -> CombinationsPruned<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&[usize]) -> bool,
+    {
+        combinations::combinations_pruned(self, k, feasible)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, each one flagged with whether it is the last one.
+    ///
+    /// Iterator element type is `(Vec<Self::Item>, bool)`. The combinations are the same
+    /// as with [`.combinations(k)`](Itertools::combinations), and the flag is `true` only for
+    /// the last one, made of the last `k` elements of the source.
+    ///
+    /// The flag is computed from the indices of the combination, without cloning any element.
+    /// To know that the source is exhausted, the iterator might take one more element
+    /// from it, which the next step would take anyway.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..4).combinations_flagged(2);
+    /// itertools::assert_equal(it, vec![
+    ///     (vec![1, 2], false),
+    ///     (vec![1, 3], false),
+    ///     (vec![2, 3], true),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_flagged(self, k: usize) -> CombinationsFlagged<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations::combinations_flagged(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, each one paired with the previous combination.
+    ///
+    /// Iterator element type is `(Option<Vec<Self::Item>>, Vec<Self::Item>)`
+    /// where the first element is `None` for the first combination.
+    /// Combinations are in the same order as [`.combinations(k)`](Itertools::combinations).
+    ///
+    /// Note: Each combination is cloned once more to be kept as the next previous one.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..4).combinations_with_prev(2);
+    /// itertools::assert_equal(it, vec![
+    ///     (None, vec![1, 2]),
+    ///     (Some(vec![1, 2]), vec![1, 3]),
+    ///     (Some(vec![1, 3]), vec![2, 3]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_with_prev(self, k: usize) -> CombinationsWithPrev<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations::combinations_with_prev(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the `Copy` elements from an iterator.
+    ///
+    /// This yields the same combinations as [`.combinations(k)`](Itertools::combinations)
+    /// but copies the elements directly instead of going through the generic `Clone`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..5).combinations_copied(3);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![1, 2, 3],
+    ///     vec![1, 2, 4],
+    ///     vec![1, 3, 4],
+    ///     vec![2, 3, 4],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_copied(self, k: usize) -> CombinationsCopied<Self>
+    where
+        Self: Sized,
+        Self::Item: Copy,
+    {
+        combinations::combinations_copied(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, where the elements of each combination are sorted by `key`.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. The iterator produces a new `Vec` per iteration,
+    /// and clones the iterator elements.
+    ///
+    /// The original elements are kept, only their order in the pool changes:
+    /// the pool is sorted once (with a stable sort) when the first combination is requested.
+    /// Combinations are then generated in lexicographic order over the sorted pool.
+    ///
+    /// Note: This forces the full buffering of the source iterator on the first call to `next`,
+    /// so it should not be used with an infinite iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec!["ccc", "a", "bb"].into_iter().combinations_by_key(2, |s| s.len());
+    /// itertools::assert_equal(it, vec![
+    ///     vec!["a", "bb"],
+    ///     vec!["a", "ccc"],
+    ///     vec!["bb", "ccc"],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_by_key<F, K>(self, k: usize, key: F) -> CombinationsByKey<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item) -> K,
+        K: Ord,
+    {
+        combinations::combinations_by_key(self, k, key)
+    }
+
+    /// Return an iterator adaptor that maps the `k`-length combinations of
+    /// the elements from an iterator with `f` while it returns `Some`.
+    ///
+    /// The closure borrows each combination as a slice: the elements are cloned
+    /// into an internal buffer that is reused, instead of a new `Vec` per combination.
+    ///
+    /// The iterator ends as soon as `f` returns `None`, and the source iterator
+    /// is not consumed any further than needed to produce that last combination.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sums = (1..6).combinations_map_while(2, |c| {
+    ///     let sum: i32 = c.iter().sum();
+    ///     if sum < 6 {
+    ///         Some(sum)
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    /// itertools::assert_equal(sums, vec![3, 4, 5]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_map_while<F, R>(self, k: usize, f: F) -> CombinationsMapWhile<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&[Self::Item]) -> Option<R>,
+    {
+        combinations::combinations_map_while(self, k, f)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator, removing the consecutive ones that `same` deems equivalent.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. Combinations are in the same order as
+    /// [`.combinations(k)`](Itertools::combinations), and `same` is called with the last
+    /// combination yielded and the next candidate.
+    ///
+    /// Like [`.dedup_by()`](Itertools::dedup_by), only *adjacent* equivalent combinations
+    /// are removed, but the removed combinations are not collected into new `Vec`s.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // Same first element.
+    /// let it = (1..5).combinations_dedup_by(2, |a, b| a[0] == b[0]);
+    /// itertools::assert_equal(it, vec![vec![1, 2], vec![2, 3], vec![3, 4]]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_dedup_by<F>(self, k: usize, same: F) -> CombinationsDedupBy<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&[Self::Item], &[Self::Item]) -> bool,
+    {
+        combinations::combinations_dedup_by(self, k, same)
+    }
+
     /// Return an iterator that iterates over the `k`-length combinations of
     /// the elements from an iterator, with replacement.
     ///
@@ -1737,6 +2394,39 @@ pub trait Itertools: Iterator {
         combinations_with_replacement::combinations_with_replacement(self, k)
     }
 
+    /// Return an iterator adaptor that iterates over the `k`-length combinations with
+    /// replacement of the elements from an iterator, each one paired with the previous one.
+    ///
+    /// Iterator element type is `(Option<Vec<Self::Item>>, Vec<Self::Item>)`
+    /// where the first element is `None` for the first combination.
+    /// Combinations are in the same order as
+    /// [`.combinations_with_replacement(k)`](Itertools::combinations_with_replacement).
+    ///
+    /// Successive combinations only differ by a suffix, which starts at
+    /// [`changed_from`](crate::structs::CombinationsWithReplacementWithPrev::changed_from).
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..3).combinations_with_replacement_with_prev(2);
+    /// itertools::assert_equal(it, vec![
+    ///     (None, vec![1, 1]),
+    ///     (Some(vec![1, 1]), vec![1, 2]),
+    ///     (Some(vec![1, 2]), vec![2, 2]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_with_replacement_with_prev(
+        self,
+        k: usize,
+    ) -> CombinationsWithReplacementWithPrev<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations_with_replacement::combinations_with_replacement_with_prev(self, k)
+    }
+
     /// Return an iterator adaptor that iterates over all k-permutations of the
     /// elements from an iterator.
     ///
@@ -1823,6 +2513,172 @@ pub trait Itertools: Iterator {
         powerset::powerset(self)
     }
 
+    /// Return an iterator adaptor that maps the subsets of the powerset of the elements
+    /// from an iterator with the closure `f`, which also receives the size of each subset
+    /// and its rank among the subsets of that size.
+    ///
+    /// `f` is called as `f(k, rank, subset)`. Subsets are in the same order as
+    /// [`.powerset()`](Itertools::powerset), so the ranks in a layer of size `k`
+    /// go from `0` to `binomial(n, k) - 1`, which is handy to index precomputed tables.
+    /// The subset is a slice of a buffer reused between calls.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "abc".chars().powerset_map_indexed(|k, rank, s| (k, rank, s.iter().collect::<String>()));
+    /// itertools::assert_equal(it, vec![
+    ///     (0, 0, "".to_string()),
+    ///     (1, 0, "a".to_string()),
+    ///     (1, 1, "b".to_string()),
+    ///     (1, 2, "c".to_string()),
+    ///     (2, 0, "ab".to_string()),
+    ///     (2, 1, "ac".to_string()),
+    ///     (2, 2, "bc".to_string()),
+    ///     (3, 0, "abc".to_string()),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn powerset_map_indexed<F, R>(self, f: F) -> PowersetMapIndexed<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(usize, usize, &[Self::Item]) -> R,
+    {
+        powerset::powerset_map_indexed(self, f)
+    }
+
+    /// Return an iterator that iterates through the powerset of the elements from an
+    /// iterator, each subset paired with its bitmask.
+    ///
+    /// Iterator element type is `(usize, Vec<Self::Item>)` where the `i`-th bit of the mask
+    /// is set if and only if the `i`-th element is in the subset. Subsets are in the same
+    /// order as [`.powerset()`](Itertools::powerset), not in increasing mask order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a subset contains an element at an index greater or equal to `usize::BITS`,
+    /// as its mask would not fit in a `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sets = (1..4).powerset_indexed();
+    /// itertools::assert_equal(sets, vec![
+    ///     (0b000, vec![]),
+    ///     (0b001, vec![1]),
+    ///     (0b010, vec![2]),
+    ///     (0b100, vec![3]),
+    ///     (0b011, vec![1, 2]),
+    ///     (0b101, vec![1, 3]),
+    ///     (0b110, vec![2, 3]),
+    ///     (0b111, vec![1, 2, 3]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn powerset_indexed(self) -> PowersetIndexed<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        powerset::powerset_indexed(self)
+    }
+
+    /// Return an iterator that iterates through the subsets of the elements from an
+    /// iterator that contain the elements at positions `forced_in` and none of the
+    /// elements at positions `excluded`.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. Each subset starts with the forced
+    /// elements (in their order in the source), followed by a subset of the free elements,
+    /// in the same order as [`.powerset()`](Itertools::powerset) over the free elements.
+    /// There are `2^(n - forced - excluded)` such subsets.
+    ///
+    /// The source is taken up to its last forced or excluded element when the first subset
+    /// is generated, then the free elements are taken as they are needed,
+    /// like with [`.powerset()`](Itertools::powerset).
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "abcd".chars().powerset_masked(&[2], &[0]);
+    /// itertools::assert_equal(it, vec![
+    ///     vec!['c'],
+    ///     vec!['c', 'b'],
+    ///     vec!['c', 'd'],
+    ///     vec!['c', 'b', 'd'],
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a position is both forced in and excluded.
+    /// Panics if a position is out of the source: right away if the length of the source
+    /// is known from its size hint, otherwise when the first subset is generated.
+    #[cfg(feature = "use_alloc")]
+    fn powerset_masked(self, forced_in: &[usize], excluded: &[usize]) -> PowersetMasked<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        powerset::powerset_masked(self, forced_in, excluded)
+    }
+
+    /// Return an iterator that iterates through the subsets of at most `max_size`
+    /// elements from an iterator.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. Subsets are in the same order as
+    /// [`.powerset()`](Itertools::powerset), which is like
+    /// `.powerset().take_while(|s| s.len() <= max_size)`, except that the larger layers
+    /// are never started: `.powerset_bounded(0)` terminates even over an infinite source.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..4).powerset_bounded(1);
+    /// itertools::assert_equal(it, vec![vec![], vec![1], vec![2], vec![3]]);
+    ///
+    /// let mut it = (0..).powerset_bounded(0);
+    /// assert_eq!(it.next(), Some(vec![]));
+    /// assert_eq!(it.next(), None);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn powerset_bounded(self, max_size: usize) -> Powerset<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        powerset::powerset_bounded(self, max_size)
+    }
+
+    /// Return an iterator that iterates through the non-empty subsets of the elements
+    /// from an iterator.
+    ///
+    /// This is like [`.powerset()`](Itertools::powerset) without the empty set,
+    /// so it has length _2^n - 1_ where _n_ is the length of the input set.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sets = (1..4).powerset_nonempty();
+    /// assert_eq!(sets.size_hint(), (7, Some(7)));
+    /// itertools::assert_equal(sets, vec![
+    ///     vec![1],
+    ///     vec![2],
+    ///     vec![3],
+    ///     vec![1, 2],
+    ///     vec![1, 3],
+    ///     vec![2, 3],
+    ///     vec![1, 2, 3],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn powerset_nonempty(self) -> Powerset<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        powerset::powerset_nonempty(self)
+    }
+
     /// Return an iterator adaptor that pads the sequence to a minimum length of
     /// `min` by filling missing elements using a closure `f`.
     ///
@@ -163,7 +163,7 @@ impl<L, R, F: FnMut(&L, &R) -> bool> OrderingOrBool<L, R> for MergeFuncLR<F, boo
     }
     fn size_hint(left: SizeHint, right: SizeHint) -> SizeHint {
         // Not ExactSizeIterator because size may be larger than usize
-        size_hint::add(left, right)
+        size_hint::saturating_add(left, right)
     }
 }
 
@@ -184,7 +184,7 @@ impl<T, F: FnMut(&T, &T) -> bool> OrderingOrBool<T, T> for F {
     }
     fn size_hint(left: SizeHint, right: SizeHint) -> SizeHint {
         // Not ExactSizeIterator because size may be larger than usize
-        size_hint::add(left, right)
+        size_hint::saturating_add(left, right)
     }
 }
 
@@ -205,7 +205,7 @@ impl<T: PartialOrd> OrderingOrBool<T, T> for MergeLte {
     }
     fn size_hint(left: SizeHint, right: SizeHint) -> SizeHint {
         // Not ExactSizeIterator because size may be larger than usize
-        size_hint::add(left, right)
+        size_hint::saturating_add(left, right)
     }
 }
 
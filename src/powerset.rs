@@ -13,6 +13,13 @@ use crate::size_hint::{self, SizeHint};
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 pub struct Powerset<I: Iterator> {
     combs: Combinations<I>,
+    /// The number of subsets yielded by `next_back`.
+    ///
+    /// Once the front meets the back, the front can be past some of these subsets,
+    /// so they are subtracted from the remaining subsets with saturation.
+    taken_back: usize,
+    /// The pool indices of the last subset yielded by `next_back`, if any.
+    back: Option<Vec<usize>>,
 }
 
 impl<I> Clone for Powerset<I>
@@ -20,7 +27,7 @@ where
     I: Clone + Iterator,
     I::Item: Clone,
 {
-    clone_fields!(combs);
+    clone_fields!(combs, taken_back, back);
 }
 
 impl<I> fmt::Debug for Powerset<I>
@@ -28,7 +35,7 @@ where
     I: Iterator + fmt::Debug,
     I::Item: fmt::Debug,
 {
-    debug_fmt_fields!(Powerset, combs);
+    debug_fmt_fields!(Powerset, combs, taken_back, back);
 }
 
 /// Create a new `Powerset` from a clonable iterator.
@@ -39,13 +46,64 @@ where
 {
     Powerset {
         combs: combinations(src, 0),
+        taken_back: 0,
+        back: None,
+    }
+}
+
+/// Create a new `Powerset` from a clonable iterator, with subsets of at most `max_size` elements.
+pub fn powerset_bounded<I>(src: I, max_size: usize) -> Powerset<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    Powerset {
+        combs: combinations(src, 0).with_max_k(max_size),
+        taken_back: 0,
+        back: None,
+    }
+}
+
+/// Create a new `Powerset` from a clonable iterator, without the empty set.
+pub fn powerset_nonempty<I>(src: I) -> Powerset<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    Powerset {
+        combs: combinations(src, 1),
+        taken_back: 0,
+        back: None,
+    }
+}
+
+impl<T> Powerset<alloc::vec::IntoIter<T>> {
+    /// Create a new `Powerset` over the elements of an already buffered `pool`.
+    ///
+    /// The elements are used directly as the pool, without going through a source iterator,
+    /// so size hints are exact from the start.
+    ///
+    /// ```
+    /// use itertools::Powerset;
+    ///
+    /// let it = Powerset::from_pool(vec!['a', 'b']);
+    /// assert_eq!(it.size_hint(), (4, Some(4)));
+    /// itertools::assert_equal(it, vec![vec![], vec!['a'], vec!['b'], vec!['a', 'b']]);
+    /// ```
+    pub fn from_pool(pool: Vec<T>) -> Self {
+        Self {
+            combs: Combinations::from_pool(pool, 0),
+            taken_back: 0,
+            back: None,
+        }
     }
 }
 
 impl<I: Iterator> Powerset<I> {
     /// Returns true if `k` has been incremented, false otherwise.
     fn increment_k(&mut self) -> bool {
-        if self.combs.k() < self.combs.n() || self.combs.k() == 0 {
+        let k = self.combs.k();
+        if k < self.combs.max_k() && (k < self.combs.n() || k == 0) {
             self.combs.reset(self.combs.k() + 1);
             true
         } else {
@@ -54,24 +112,174 @@ impl<I: Iterator> Powerset<I> {
     }
 }
 
-impl<I> Iterator for Powerset<I>
+impl<I> Powerset<I>
 where
     I: Iterator,
     I::Item: Clone,
 {
-    type Item = Vec<I::Item>;
+    /// Collects the remaining subsets into a `Vec`, allocated once.
+    ///
+    /// The source is buffered first so that the number of subsets is known, even when
+    /// the size hint of the source is not exact. If that number overflows a `usize`,
+    /// the outer `Vec` grows as with [`collect`](Iterator::collect).
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let subsets = (1..4).filter(|x| x % 2 == 1).powerset().into_vec();
+    /// assert_eq!(subsets, vec![vec![], vec![1], vec![3], vec![1, 3]]);
+    /// ```
+    pub fn into_vec(mut self) -> Vec<Vec<I::Item>> {
+        self.combs.prefill_pool(usize::MAX);
+        let mut subsets = match self.size_hint() {
+            (low, Some(upp)) if low == upp => Vec::with_capacity(low),
+            _ => Vec::new(),
+        };
+        subsets.extend(self);
+        subsets
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(elt) = self.combs.next() {
-            Some(elt)
-        } else if self.increment_k() {
-            self.combs.next()
+    /// Counts the remaining subsets, but stops at `limit + 1`.
+    ///
+    /// This answers "are there more than `limit` subsets?" without having to consume the
+    /// whole source (which might be huge or even infinite): the count of subsets
+    /// grows with each element of the source, so the source is only consumed until the
+    /// count exceeds `limit`. It also avoids the overflow of [`count`](Iterator::count)
+    /// on sources with at least `usize::BITS` elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert_eq!((0..3).powerset().count_up_to(10), 8);
+    /// assert_eq!((0..100).powerset().count_up_to(10), 11);
+    /// assert_eq!((0..).powerset().count_up_to(1000), 1001);
+    /// ```
+    pub fn count_up_to(mut self, limit: usize) -> usize {
+        let cap = limit.saturating_add(1);
+        loop {
+            let n = self.combs.n();
+            let count = self.combs.remaining(n).and_then(|count| {
+                count.checked_add(remaining_for(n, self.combs.k(), self.combs.max_k())?)
+            });
+            let count = match count {
+                // The subsets yielded from the back are only possible with a fully loaded pool.
+                Some(count) if count.saturating_sub(self.taken_back) < cap => {
+                    count.saturating_sub(self.taken_back)
+                }
+                _ => return cap,
+            };
+            self.combs.prefill_pool(n + 1);
+            if self.combs.n() == n {
+                // The source is exhausted so the count is exact.
+                return count;
+            }
+        }
+    }
+
+    /// Consumes the iterator and counts the remaining subsets, like `count`, except that
+    /// the count saturates at `usize::MAX` instead of panicking if it overflows a `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// assert_eq!((0..10).powerset().count_saturating(), 1 << 10);
+    /// assert_eq!((0..200).powerset().count_saturating(), usize::MAX);
+    /// ```
+    pub fn count_saturating(self) -> usize {
+        self.checked_count().unwrap_or(usize::MAX)
+    }
+
+    /// Calls `f` once per layer of subsets of the same size `k`, in increasing order of `k`,
+    /// with an iterator over the subsets of that layer.
+    ///
+    /// The layers start at the current one, which might have been partially consumed.
+    /// Subsets of a layer left unconsumed by `f` are skipped.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut layers = Vec::new();
+    /// (1..4).powerset().for_each_layer(|k, layer| layers.push((k, layer.count())));
+    /// assert_eq!(layers, vec![(0, 1), (1, 3), (2, 3), (3, 1)]);
+    /// ```
+    pub fn for_each_layer<F>(self, mut f: F)
+    where
+        F: FnMut(usize, &mut dyn Iterator<Item = Vec<I::Item>>),
+    {
+        // The subsets yielded from the back are not yielded again.
+        let mut remaining = if self.taken_back > 0 {
+            Some(self.size_hint().0)
         } else {
             None
+        };
+        let mut combs = self.combs;
+        loop {
+            let k = combs.k();
+            // The layer might not have loaded the pool.
+            combs.prefill_pool(k);
+            if k > combs.n() || remaining == Some(0) {
+                break;
+            }
+            if let Some(remaining) = &mut remaining {
+                // The pool is fully loaded so the size hint is exact.
+                let len = combs.size_hint().0.min(*remaining);
+                *remaining -= len;
+                f(k, &mut combs.by_ref().take(len));
+            } else {
+                f(k, &mut combs);
+            }
+            if k == combs.max_k() {
+                // `reset` would clamp to the same layer.
+                break;
+            }
+            combs.reset(k + 1);
         }
     }
 
-    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+    /// Advances to the next subset, the one of the inner combinations.
+    ///
+    /// Returns true if there are no more subsets, false otherwise.
+    fn advance(&mut self) -> bool {
+        let done = if !self.combs.advance() {
+            false
+        } else if self.increment_k() {
+            self.combs.advance()
+        } else {
+            true
+        };
+        done || self
+            .back
+            .as_deref()
+            .map_or(false, |back| self.yielded(back))
+    }
+
+    /// Returns true if the subset of pool indices `subset` was yielded from the front.
+    ///
+    /// Subsets are ordered by size, then lexicographically by indices.
+    fn yielded(&self, subset: &[usize]) -> bool {
+        let k = self.combs.k();
+        if self.combs.is_started() {
+            (k, self.combs.indices()) >= (subset.len(), subset)
+        } else {
+            // The first subset of the layer is not yielded yet.
+            k > subset.len()
+        }
+    }
+
+    /// `nth` without considering the subsets yielded from the back.
+    fn nth_from_front(&mut self, mut n: usize) -> Option<Vec<I::Item>> {
+        // When the length of the source is known, skip entire layers.
+        let (len, len_max) = self.combs.src().size_hint();
+        if len_max == Some(len) {
+            if let (remaining, Some(remaining_max)) = self.combs.size_hint() {
+                if remaining == remaining_max && n >= remaining {
+                    self.combs.prefill_pool(len);
+                    if self.combs.n() == len {
+                        return self.nth_in_next_layers(n - remaining);
+                    }
+                }
+            }
+        }
         loop {
             match self.combs.try_nth(n) {
                 Ok(item) => return Some(item),
@@ -85,33 +293,104 @@ where
         }
     }
 
-    fn size_hint(&self) -> SizeHint {
+    /// Counts the remaining subsets, or returns `None` if the count overflows a `usize`.
+    fn checked_count(self) -> Option<usize> {
+        let (k, max_k) = (self.combs.k(), self.combs.max_k());
+        let (n, combs_count) = self.combs.n_and_checked_count();
+        let count = combs_count?.checked_add(remaining_for(n, k, max_k)?)?;
+        Some(count.saturating_sub(self.taken_back))
+    }
+
+    /// `size_hint` without considering the subsets yielded from the back.
+    fn size_hint_from_front(&self) -> SizeHint {
         let k = self.combs.k();
         // Total bounds for source iterator.
         let (n_min, n_max) = self.combs.src().size_hint();
-        let low = remaining_for(n_min, k).unwrap_or(usize::MAX);
-        let upp = n_max.and_then(|n| remaining_for(n, k));
-        size_hint::add(self.combs.size_hint(), (low, upp))
+        let low = remaining_for(n_min, k, self.combs.max_k()).unwrap_or(usize::MAX);
+        let upp = n_max.and_then(|n| remaining_for(n, k, self.combs.max_k()));
+        size_hint::saturating_add(self.combs.size_hint(), (low, upp))
     }
 
+    /// Returns the `n`-th subset after the current layer, with the pool fully buffered.
+    fn nth_in_next_layers(&mut self, mut n: usize) -> Option<Vec<I::Item>> {
+        let len = self.combs.n();
+        let last_k = len.min(self.combs.max_k());
+        for k in self.combs.k() + 1..=last_k {
+            match checked_binomial(len, k) {
+                Some(count) if n >= count => n -= count,
+                _ => {
+                    self.combs.reset(k);
+                    self.combs.jump_to(n);
+                    return Some(self.combs.src().get_at(self.combs.indices()));
+                }
+            }
+        }
+        // All subsets have been skipped: the last one is considered generated.
+        self.combs.reset(last_k);
+        self.combs.jump_to_last();
+        None
+    }
+}
+
+impl<I> Iterator for Powerset<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.advance() {
+            return None;
+        }
+        Some(self.combs.src().get_at(self.combs.indices()))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let subset = self.nth_from_front(n)?;
+        // Do not go past the subsets yielded from the back.
+        match &self.back {
+            Some(back) if self.yielded(back) => None,
+            _ => Some(subset),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        size_hint::sub_scalar(self.size_hint_from_front(), self.taken_back)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the number of remaining subsets overflows a `usize`,
+    /// see [`Powerset::count_saturating`] for an alternative.
     fn count(self) -> usize {
-        let k = self.combs.k();
-        let (n, combs_count) = self.combs.n_and_count();
-        combs_count + remaining_for(n, k).unwrap()
+        self.checked_count()
+            .unwrap_or_else(|| panic!("powerset too large to count"))
     }
 
     fn fold<B, F>(self, mut init: B, mut f: F) -> B
     where
         F: FnMut(B, Self::Item) -> B,
     {
+        if self.taken_back > 0 {
+            // Layers are not folded entirely.
+            for subset in self {
+                init = f(init, subset);
+            }
+            return init;
+        }
+        let max_k = self.combs.max_k();
         let mut it = self.combs;
         if it.k() == 0 {
             init = it.by_ref().fold(init, &mut f);
+            if max_k == 0 {
+                return init;
+            }
             it.reset(1);
         }
         init = it.by_ref().fold(init, &mut f);
         // n is now known for sure because k >= 1 and all k-combinations have been generated.
-        for k in it.k() + 1..=it.n() {
+        for k in it.k() + 1..=it.n().min(max_k) {
             it.reset(k);
             init = it.by_ref().fold(init, &mut f);
         }
@@ -119,6 +398,64 @@ where
     }
 }
 
+/// Iterating from the back buffers the entire source iterator first.
+///
+/// ```
+/// use itertools::Itertools;
+///
+/// let mut it = (1..4).powerset();
+/// assert_eq!(it.next_back(), Some(vec![1, 2, 3]));
+/// assert_eq!(it.next_back(), Some(vec![2, 3]));
+/// assert_eq!(it.next(), Some(vec![]));
+/// itertools::assert_equal(it.rev(), vec![
+///     vec![1, 3],
+///     vec![1, 2],
+///     vec![3],
+///     vec![2],
+///     vec![1],
+/// ]);
+/// ```
+impl<I> DoubleEndedIterator for Powerset<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.combs.prefill_pool(usize::MAX);
+        let n = self.combs.n();
+        // Step back from the previous subset, without counting the subsets.
+        let indices = match &self.back {
+            None => {
+                let k = n.min(self.combs.max_k());
+                (n - k..n).collect()
+            }
+            Some(back) => {
+                let mut indices = back.clone();
+                if !prev_subset(n, &mut indices) {
+                    return None;
+                }
+                indices
+            }
+        };
+        if self.yielded(&indices) {
+            return None;
+        }
+        let subset = self.combs.src().get_at(&indices);
+        self.back = Some(indices);
+        self.taken_back += 1;
+        Some(subset)
+    }
+}
+
+/// The length is exact as long as `2^n` fits in a `usize`, where `n` is the length of the source.
+/// Otherwise, calling `len` panics.
+impl<I> ExactSizeIterator for Powerset<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
+}
+
 impl<I> FusedIterator for Powerset<I>
 where
     I: Iterator,
@@ -126,6 +463,425 @@ where
 {
 }
 
-fn remaining_for(n: usize, k: usize) -> Option<usize> {
-    (k + 1..=n).try_fold(0usize, |sum, i| sum.checked_add(checked_binomial(n, i)?))
+/// An iterator to iterate through the powerset of the elements from an iterator,
+/// each subset paired with its bitmask.
+///
+/// See [`.powerset_indexed()`](crate::Itertools::powerset_indexed) for more
+/// information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct PowersetIndexed<I: Iterator> {
+    powerset: Powerset<I>,
 }
+
+impl<I> Clone for PowersetIndexed<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(powerset);
+}
+
+impl<I> fmt::Debug for PowersetIndexed<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(PowersetIndexed, powerset);
+}
+
+/// Create a new `PowersetIndexed` from a clonable iterator.
+pub fn powerset_indexed<I>(src: I) -> PowersetIndexed<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    PowersetIndexed {
+        powerset: powerset(src),
+    }
+}
+
+impl<I> Iterator for PowersetIndexed<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (usize, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let subset = self.powerset.next()?;
+        // The indices of the combination that was just generated.
+        let mask = self.powerset.combs.indices().iter().fold(0, |mask, i| {
+            let bit = 1_usize
+                .checked_shl(*i as u32)
+                .expect("powerset_indexed: more elements than bits in a usize");
+            mask | bit
+        });
+        Some((mask, subset))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.powerset.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.powerset.count()
+    }
+}
+
+impl<I> FusedIterator for PowersetIndexed<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// Sets `indices` to the previous subset of a pool of `n` elements, the last one of the
+/// previous layer if `indices` is the first one of its layer.
+///
+/// Returns false, leaving `indices` untouched, if there is no previous subset.
+fn prev_subset(n: usize, indices: &mut Vec<usize>) -> bool {
+    let k = indices.len();
+    // The rightmost index that can decrease.
+    let i = (0..k)
+        .rev()
+        .find(|&i| indices[i] > if i == 0 { 0 } else { indices[i - 1] + 1 });
+    match i {
+        Some(i) => {
+            indices[i] -= 1;
+            for (j, index) in indices.iter_mut().enumerate().skip(i + 1) {
+                *index = n - k + j;
+            }
+        }
+        None if k == 0 => return false,
+        None => {
+            indices.clear();
+            indices.extend(n + 1 - k..n);
+        }
+    }
+    true
+}
+
+/// The number of subsets in the layers after `k`, for a pool of `n` elements and
+/// subsets of at most `max_size` elements, or None if it would overflow.
+fn remaining_for(n: usize, k: usize, max_size: usize) -> Option<usize> {
+    (k + 1..=n.min(max_size)).try_fold(0usize, |sum, i| sum.checked_add(checked_binomial(n, i)?))
+}
+
+/// An iterator adaptor that maps the subsets of the powerset of an iterator,
+/// with their size and rank in their layer.
+///
+/// See [`.powerset_map_indexed()`](crate::Itertools::powerset_map_indexed) for more
+/// information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct PowersetMapIndexed<I: Iterator, F> {
+    powerset: Powerset<I>,
+    buffer: Vec<I::Item>,
+    /// The size and rank in its layer of the previous subset.
+    prev: Option<(usize, usize)>,
+    f: F,
+}
+
+impl<I, F> Clone for PowersetMapIndexed<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(powerset, buffer, prev, f);
+}
+
+impl<I, F> fmt::Debug for PowersetMapIndexed<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(PowersetMapIndexed, powerset, prev);
+}
+
+/// Create a new `PowersetMapIndexed` from a clonable iterator.
+pub fn powerset_map_indexed<I, F, R>(src: I, f: F) -> PowersetMapIndexed<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(usize, usize, &[I::Item]) -> R,
+{
+    PowersetMapIndexed {
+        powerset: powerset(src),
+        buffer: Vec::new(),
+        prev: None,
+        f,
+    }
+}
+
+impl<I, F, R> Iterator for PowersetMapIndexed<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(usize, usize, &[I::Item]) -> R,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.powerset.advance() {
+            return None;
+        }
+        let combs = &self.powerset.combs;
+        let k = combs.k();
+        let rank = match self.prev {
+            Some((prev_k, prev_rank)) if prev_k == k => prev_rank + 1,
+            _ => 0,
+        };
+        self.prev = Some((k, rank));
+        let pool = combs.src();
+        self.buffer.clear();
+        self.buffer
+            .extend(combs.indices().iter().map(|i| pool[*i].clone()));
+        Some((self.f)(k, rank, &self.buffer))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.powerset.size_hint()
+    }
+}
+
+impl<I, F, R> FusedIterator for PowersetMapIndexed<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(usize, usize, &[I::Item]) -> R,
+{
+}
+
+/// An iterator to iterate through the subsets of the elements from an iterator,
+/// some of them being always included and others never.
+///
+/// See [`.powerset_masked()`](crate::Itertools::powerset_masked) for more
+/// information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct PowersetMasked<I: Iterator> {
+    /// The elements always included, once taken from the source.
+    forced: Vec<I::Item>,
+    /// The source and the positions of the forced elements in increasing order,
+    /// until the forced elements are taken from it.
+    pending: Option<(MaskedFree<I>, Vec<usize>)>,
+    /// The subsets of the free elements, once the forced elements are taken.
+    powerset: Option<Powerset<MaskedFree<I>>>,
+}
+
+impl<I> Clone for PowersetMasked<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(forced, pending, powerset);
+}
+
+impl<I> fmt::Debug for PowersetMasked<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(PowersetMasked, forced, pending, powerset);
+}
+
+/// Create a new `PowersetMasked` from an iterator.
+pub fn powerset_masked<I>(src: I, forced_in: &[usize], excluded: &[usize]) -> PowersetMasked<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    let mut forced_in = forced_in.to_vec();
+    forced_in.sort_unstable();
+    forced_in.dedup();
+    let mut excluded = excluded.to_vec();
+    excluded.sort_unstable_by(|a, b| b.cmp(a));
+    excluded.dedup();
+    if let Some(index) = forced_in
+        .iter()
+        .find(|&&index| excluded.binary_search_by(|x| index.cmp(x)).is_ok())
+    {
+        panic!("index {} is both forced in and excluded", index);
+    }
+    // The positions can be checked right away when the length of the source is known.
+    if let (len, Some(upp)) = src.size_hint() {
+        match forced_in.last().max(excluded.first()) {
+            Some(&last) if len == upp && last >= len => panic!(
+                "index {} is out of range for a source of {} elements",
+                last, len
+            ),
+            _ => {}
+        }
+    }
+    let src = MaskedFree {
+        taken: Vec::new().into_iter(),
+        iter: src,
+        index: 0,
+        excluded,
+    };
+    PowersetMasked {
+        forced: Vec::new(),
+        pending: Some((src, forced_in)),
+        powerset: None,
+    }
+}
+
+impl<I> PowersetMasked<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    /// Takes the source up to its last masked element if not done yet,
+    /// and returns the subsets of the free elements.
+    fn free_subsets(&mut self) -> &mut Powerset<MaskedFree<I>> {
+        if let Some((mut src, forced_in)) = self.pending.take() {
+            self.forced = src.take_masked(&forced_in);
+            self.powerset = Some(powerset(src));
+        }
+        self.powerset
+            .as_mut()
+            .expect("the forced elements are taken")
+    }
+}
+
+impl<I> Iterator for PowersetMasked<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let subset = self.free_subsets().next()?;
+        let mut elts = Vec::with_capacity(self.forced.len() + subset.len());
+        elts.extend_from_slice(&self.forced);
+        elts.extend(subset);
+        Some(elts)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match (&self.pending, &self.powerset) {
+            (Some((src, forced_in)), _) => {
+                // There are `2^free` subsets of the free elements, but none if a masked
+                // position is out of range, which is only checked upfront for a known length.
+                let (low, upp) = size_hint::sub_scalar(src.size_hint(), forced_in.len());
+                let pow2 = |n: usize| {
+                    if n < usize::BITS as usize {
+                        Some(1 << n)
+                    } else {
+                        None
+                    }
+                };
+                let upp = upp.and_then(pow2);
+                match src.iter.size_hint() {
+                    (len, Some(len_upp)) if len == len_upp => {
+                        (pow2(low).unwrap_or(usize::MAX), upp)
+                    }
+                    _ => (0, upp),
+                }
+            }
+            (None, Some(powerset)) => powerset.size_hint(),
+            (None, None) => unreachable!("the forced elements are taken"),
+        }
+    }
+
+    fn count(mut self) -> usize {
+        self.free_subsets();
+        self.powerset.map_or(0, Powerset::count)
+    }
+}
+
+impl<I> ExactSizeIterator for PowersetMasked<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
+}
+
+impl<I> FusedIterator for PowersetMasked<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator over the elements of a source that are neither forced in nor excluded.
+#[derive(Debug)]
+struct MaskedFree<I: Iterator> {
+    /// The free elements taken from the source along with the forced ones.
+    taken: alloc::vec::IntoIter<I::Item>,
+    iter: I,
+    /// The position in the source of the next element of `iter`.
+    index: usize,
+    /// The positions of the excluded elements not taken from the source yet,
+    /// in decreasing order. Empty once the free elements are yielded.
+    excluded: Vec<usize>,
+}
+
+impl<I> Clone for MaskedFree<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(taken, iter, index, excluded);
+}
+
+impl<I: Iterator> MaskedFree<I> {
+    /// Takes the next element from the source along with its position.
+    ///
+    /// Panics if the source ends before the position `needed`.
+    fn next_indexed(&mut self, needed: usize) -> (usize, I::Item) {
+        let index = self.index;
+        match self.iter.next() {
+            Some(elt) => {
+                self.index += 1;
+                (index, elt)
+            }
+            None => panic!(
+                "index {} is out of range for a source of {} elements",
+                needed, index
+            ),
+        }
+    }
+
+    /// Takes the source up to its last masked position, so that every position is checked,
+    /// and returns the elements at the positions `forced_in`, in increasing order.
+    /// The free elements taken along are kept to be yielded first.
+    fn take_masked(&mut self, forced_in: &[usize]) -> Vec<I::Item> {
+        let mut forced = Vec::with_capacity(forced_in.len());
+        let mut taken = Vec::new();
+        if let Some(&last) = forced_in.last().max(self.excluded.first()) {
+            let mut forced_in = forced_in.iter().peekable();
+            loop {
+                let (index, elt) = self.next_indexed(last);
+                if forced_in.next_if_eq(&&index).is_some() {
+                    forced.push(elt);
+                } else if self.excluded.last() == Some(&index) {
+                    self.excluded.pop();
+                } else {
+                    taken.push(elt);
+                }
+                if index == last {
+                    break;
+                }
+            }
+        }
+        self.taken = taken.into_iter();
+        forced
+    }
+}
+
+impl<I: Iterator> Iterator for MaskedFree<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The excluded elements are all behind, the rest of the source is free.
+        self.taken.next().or_else(|| self.iter.next())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let rest = size_hint::sub_scalar(self.iter.size_hint(), self.excluded.len());
+        size_hint::add_scalar(rest, self.taken.len())
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for MaskedFree<I> {}
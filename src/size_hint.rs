@@ -1,14 +1,16 @@
 //! Arithmetic on `Iterator.size_hint()` values.
 //!
+//! Overflow policy: lower bounds saturate at `usize::MAX`, upper bounds become `None`
+//! (unknown) when they overflow, and no operation ever wraps or panics.
 
 use std::cmp;
 
 /// `SizeHint` is the return type of `Iterator::size_hint()`.
 pub type SizeHint = (usize, Option<usize>);
 
-/// Add `SizeHint` correctly.
+/// Add `SizeHint`: the lower bound saturates, the upper bound becomes `None` on overflow.
 #[inline]
-pub fn add(a: SizeHint, b: SizeHint) -> SizeHint {
+pub fn saturating_add(a: SizeHint, b: SizeHint) -> SizeHint {
     let min = a.0.saturating_add(b.0);
     let max = match (a.1, b.1) {
         (Some(x), Some(y)) => x.checked_add(y),
@@ -36,9 +38,9 @@ pub fn sub_scalar(sh: SizeHint, x: usize) -> SizeHint {
     (low, hi)
 }
 
-/// Multiply `SizeHint` correctly
+/// Multiply `SizeHint`: the lower bound saturates, the upper bound becomes `None` on overflow.
 #[inline]
-pub fn mul(a: SizeHint, b: SizeHint) -> SizeHint {
+pub fn saturating_mul(a: SizeHint, b: SizeHint) -> SizeHint {
     let low = a.0.saturating_mul(b.0);
     let hi = match (a.1, b.1) {
         (Some(x), Some(y)) => x.checked_mul(y),
@@ -88,7 +90,36 @@ pub fn min(a: SizeHint, b: SizeHint) -> SizeHint {
 
 #[test]
 fn mul_size_hints() {
-    assert_eq!(mul((3, Some(4)), (3, Some(4))), (9, Some(16)));
-    assert_eq!(mul((3, Some(4)), (usize::MAX, None)), (usize::MAX, None));
-    assert_eq!(mul((3, None), (0, Some(0))), (0, Some(0)));
+    assert_eq!(saturating_mul((3, Some(4)), (3, Some(4))), (9, Some(16)));
+    assert_eq!(
+        saturating_mul((3, Some(4)), (usize::MAX, None)),
+        (usize::MAX, None)
+    );
+    assert_eq!(saturating_mul((3, None), (0, Some(0))), (0, Some(0)));
+    assert_eq!(
+        saturating_mul((usize::MAX, Some(usize::MAX)), (1, Some(1))),
+        (usize::MAX, Some(usize::MAX))
+    );
+    assert_eq!(
+        saturating_mul((usize::MAX, Some(usize::MAX)), (2, Some(2))),
+        (usize::MAX, None)
+    );
+    assert_eq!(
+        saturating_mul((0, Some(0)), (usize::MAX, None)),
+        (0, Some(0))
+    );
+}
+
+#[test]
+fn saturating_add_size_hints() {
+    assert_eq!(saturating_add((3, Some(4)), (3, Some(4))), (6, Some(8)));
+    assert_eq!(
+        saturating_add((usize::MAX, Some(usize::MAX)), (0, Some(0))),
+        (usize::MAX, Some(usize::MAX))
+    );
+    assert_eq!(
+        saturating_add((usize::MAX, Some(usize::MAX)), (1, Some(1))),
+        (usize::MAX, None)
+    );
+    assert_eq!(saturating_add((1, Some(1)), (2, None)), (3, None));
 }
@@ -0,0 +1,178 @@
+//! Count heap allocations made by some adaptors.
+//!
+//! Each test runs in its own thread, so allocations are counted per thread.
+
+use itertools::Itertools;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the result of `f` and the number of (re)allocations it made.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let res = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    (res, after - before)
+}
+
+#[test]
+fn multi_cartesian_product_axes() {
+    let axes = [0..2, 0..3, 0..4, 0..5, 0..6];
+    let (_product, nb) = count_allocations(|| axes.iter().cloned().multi_cartesian_product());
+    assert_eq!(nb, 1);
+}
+
+#[test]
+fn multi_cartesian_product_with_axes() {
+    let axes = [0..2, 0..3, 0..4, 0..5, 0..6];
+    let (_product, nb) = count_allocations(|| {
+        axes.iter()
+            .filter(|_| true)
+            .cloned()
+            .multi_cartesian_product_with_axes(axes.len())
+    });
+    assert_eq!(nb, 1);
+}
+
+#[test]
+fn combinations_clone_from() {
+    let mut src = (0..10).combinations(3);
+    // Fully load the pool.
+    src.nth(20);
+    let mut dst = src.clone();
+    for _ in 0..50 {
+        src.next();
+        let ((), nb) = count_allocations(|| dst.clone_from(&src));
+        assert_eq!(nb, 0);
+        assert_eq!(dst.clone().next(), src.clone().next());
+    }
+    let ((), nb) = count_allocations(|| dst = src.clone());
+    assert_eq!(nb, 2);
+}
+
+#[test]
+fn combinations_with_replacement_clone_from() {
+    let mut src = (0..10).combinations_with_replacement(3);
+    // Fully load the pool.
+    src.nth(20);
+    let mut dst = src.clone();
+    for _ in 0..50 {
+        src.next();
+        let ((), nb) = count_allocations(|| dst.clone_from(&src));
+        assert_eq!(nb, 0);
+        assert_eq!(dst.clone().next(), src.clone().next());
+    }
+    let ((), nb) = count_allocations(|| dst = src.clone());
+    assert_eq!(nb, 2);
+}
+
+#[test]
+fn combinations_with_replacement_into_vec() {
+    let src = || (0..6).filter(|_| true);
+    let (combs, nb) = count_allocations(|| src().combinations_with_replacement(3).into_vec());
+    // The multiset coefficient `((6 + 3 - 1) choose 3)`.
+    assert_eq!(combs.len(), 56);
+    assert!(combs.capacity() >= combs.len());
+    // The outer vector, the combinations, then the pool buffer.
+    let internal = nb - 1 - 56;
+    assert!(internal <= 4, "{} internal allocations", internal);
+    let (_, nb_collect) =
+        count_allocations(|| src().combinations_with_replacement(3).collect_vec());
+    assert!(nb < nb_collect, "{} >= {}", nb, nb_collect);
+    // Too many combinations to be counted: the outer vector is not pre-sized.
+    let mut it = (0..100).combinations_with_replacement(20);
+    it.next();
+    assert_eq!(it.size_hint().1, None);
+}
+
+#[test]
+fn combinations_next_into() {
+    let mut it = (0..10).combinations(3);
+    let mut buf = Vec::with_capacity(3);
+    let (nb_combinations, nb) = count_allocations(|| {
+        let mut nb = 0;
+        while it.next_into(&mut buf) {
+            nb += 1;
+        }
+        nb
+    });
+    assert_eq!(nb_combinations, 120);
+    // Only the pool allocates while it grows.
+    assert!(nb <= 4, "{} allocations", nb);
+    // Whereas `next` allocates a new vector for each combination.
+    let ((), nb_next) = count_allocations(|| (0..10).combinations(3).for_each(drop));
+    assert!(nb_next >= 120, "{} allocations", nb_next);
+}
+
+#[test]
+fn multi_cartesian_product_collect() {
+    let axes = [0..4, 0..5, 0..6];
+    let it = axes.iter().cloned().multi_cartesian_product();
+    assert_eq!(it.size_hint(), (120, Some(120)));
+    let (v, nb) = count_allocations(|| it.collect_vec());
+    assert_eq!(v.len(), 120);
+    // The exact size hint pre-sizes the outer vector: one allocation for it,
+    // one per product and one to keep the current product.
+    assert_eq!(nb, 1 + 120 + 1);
+}
+
+#[test]
+fn multi_cartesian_product_fill() {
+    let axes = [0..4, 0..5, 0..6];
+    let it = axes
+        .iter()
+        .cloned()
+        .multi_cartesian_product_fill(|p, buf| buf.extend_from_slice(p));
+    let mut sum = 0;
+    let ((), nb) = count_allocations(|| it.for_each_filled(|buf| sum += buf.iter().sum::<i32>()));
+    assert_eq!(sum, 720);
+    // One allocation for the current product and one for the buffer.
+    assert_eq!(nb, 2);
+}
+
+#[test]
+fn powerset_internal_buffers() {
+    let (nb_subsets, nb) = count_allocations(|| (0..16).powerset().fold(0, |n, _| n + 1));
+    assert_eq!(nb_subsets, 1 << 16);
+    // Each non-empty subset is a new vector, the empty one does not allocate.
+    let internal = nb - (nb_subsets - 1);
+    // The pool and the indices grow geometrically, not once per layer.
+    assert!(internal <= 8, "{} internal allocations", internal);
+}
+
+#[test]
+fn powerset_into_vec() {
+    let src = || (0..8).filter(|_| true);
+    let (subsets, nb) = count_allocations(|| src().powerset().into_vec());
+    assert_eq!(subsets.len(), 1 << 8);
+    assert!(subsets.capacity() >= subsets.len());
+    // The outer vector, the non-empty subsets, then the pool and indices buffers.
+    let internal = nb - 1 - ((1 << 8) - 1);
+    assert!(internal <= 8, "{} internal allocations", internal);
+    let (_, nb_collect) = count_allocations(|| src().powerset().collect_vec());
+    assert!(nb < nb_collect, "{} >= {}", nb, nb_collect);
+}
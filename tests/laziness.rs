@@ -154,9 +154,25 @@ must_use_tests! {
     cartesian_product {
         let _ = Panicking.cartesian_product(Panicking);
     }
+    multi_cartesian_product_weighted {
+        let _ = vec![Panicking, Panicking, Panicking]
+            .into_iter()
+            .map(|it| it.map(|x| (x, 1.0)))
+            .multi_cartesian_product_weighted();
+    }
+    multi_cartesian_product_fill {
+        let _ = vec![Panicking, Panicking, Panicking]
+            .into_iter()
+            .multi_cartesian_product_fill(|p, buf| buf.extend_from_slice(p));
+    }
     multi_cartesian_product {
         let _ = vec![Panicking, Panicking, Panicking].into_iter().multi_cartesian_product();
     }
+    multi_cartesian_product_with_axes {
+        let _ = vec![Panicking, Panicking, Panicking]
+            .into_iter()
+            .multi_cartesian_product_with_axes(3);
+    }
     coalesce {
         let _ = Panicking.coalesce(|x, y| if x == y { Ok(x) } else { Err((x, y)) });
     }
@@ -212,11 +228,91 @@ must_use_tests! {
         let _ = Panicking.combinations(1);
         let _ = Panicking.combinations(2);
     }
+    combinations_group_by_first {
+        let _ = Panicking.combinations(0).group_by_first();
+        let _ = Panicking.combinations(1).group_by_first();
+        let _ = Panicking.combinations(2).group_by_first();
+    }
+    combinations_set {
+        let _ = Panicking.combinations_set(0);
+        let _ = Panicking.combinations_set(1);
+        let _ = Panicking.combinations_set(2);
+    }
+    combinations_hash_set {
+        let _ = Panicking.combinations_hash_set(0);
+        let _ = Panicking.combinations_hash_set(1);
+        let _ = Panicking.combinations_hash_set(2);
+    }
+    combinations_with_complement {
+        let _ = Panicking.combinations_with_complement(0);
+        let _ = Panicking.combinations_with_complement(1);
+        let _ = Panicking.combinations_with_complement(2);
+    }
+    combinations_with_prev {
+        let _ = Panicking.combinations_with_prev(0);
+        let _ = Panicking.combinations_with_prev(1);
+        let _ = Panicking.combinations_with_prev(2);
+    }
+    combinations_colex {
+        let _ = Panicking.combinations_colex(0);
+        let _ = Panicking.combinations_colex(1);
+        let _ = Panicking.combinations_colex(2);
+    }
+    combinations_pruned {
+        let _ = Panicking.combinations_pruned(0, |_| true);
+        let _ = Panicking.combinations_pruned(1, |_| true);
+        let _ = Panicking.combinations_pruned(2, |_| true);
+    }
+    combinations_flagged {
+        let _ = Panicking.combinations_flagged(0);
+        let _ = Panicking.combinations_flagged(1);
+        let _ = Panicking.combinations_flagged(2);
+    }
+    combinations_dedup_by {
+        let _ = Panicking.combinations_dedup_by(0, |a, b| a == b);
+        let _ = Panicking.combinations_dedup_by(1, |a, b| a == b);
+        let _ = Panicking.combinations_dedup_by(2, |a, b| a == b);
+    }
+    combinations_stride {
+        let _ = Panicking.combinations_stride(0, 1);
+        let _ = Panicking.combinations_stride(1, 2);
+        let _ = Panicking.combinations_stride(2, 3);
+    }
+    combinations_hash {
+        let _ = Panicking.combinations_hash(0);
+        let _ = Panicking.combinations_hash(1);
+        let _ = Panicking.combinations_hash(2);
+    }
+    combinations_hash_with {
+        let _ = Panicking.combinations_hash_with(0, std::collections::hash_map::RandomState::new());
+        let _ = Panicking.combinations_hash_with(1, std::collections::hash_map::RandomState::new());
+        let _ = Panicking.combinations_hash_with(2, std::collections::hash_map::RandomState::new());
+    }
+    combinations_copied {
+        let _ = Panicking.combinations_copied(0);
+        let _ = Panicking.combinations_copied(1);
+        let _ = Panicking.combinations_copied(2);
+    }
+    combinations_by_key {
+        let _ = Panicking.combinations_by_key(0, |x| *x);
+        let _ = Panicking.combinations_by_key(1, |x| *x);
+        let _ = Panicking.combinations_by_key(2, |x| *x);
+    }
+    combinations_map_while {
+        let _ = Panicking.combinations_map_while(0, |c| Some(c.len()));
+        let _ = Panicking.combinations_map_while(1, |c| Some(c.len()));
+        let _ = Panicking.combinations_map_while(2, |c| Some(c.len()));
+    }
     combinations_with_replacement {
         let _ = Panicking.combinations_with_replacement(0);
         let _ = Panicking.combinations_with_replacement(1);
         let _ = Panicking.combinations_with_replacement(2);
     }
+    combinations_with_replacement_with_prev {
+        let _ = Panicking.combinations_with_replacement_with_prev(0);
+        let _ = Panicking.combinations_with_replacement_with_prev(1);
+        let _ = Panicking.combinations_with_replacement_with_prev(2);
+    }
     permutations {
         let _ = Panicking.permutations(0);
         let _ = Panicking.permutations(1);
@@ -225,6 +321,22 @@ must_use_tests! {
     powerset {
         let _ = Panicking.powerset();
     }
+    powerset_map_indexed {
+        let _ = Panicking.powerset_map_indexed(|k, rank, _| (k, rank));
+    }
+    powerset_indexed {
+        let _ = Panicking.powerset_indexed();
+    }
+    powerset_masked {
+        let _ = Panicking.powerset_masked(&[], &[]);
+    }
+    powerset_bounded {
+        let _ = Panicking.powerset_bounded(0);
+        let _ = Panicking.powerset_bounded(2);
+    }
+    powerset_nonempty {
+        let _ = Panicking.powerset_nonempty();
+    }
     pad_using {
         let _ = Panicking.pad_using(25, |_| 10);
     }
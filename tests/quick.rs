@@ -455,6 +455,37 @@ quickcheck! {
         itertools::assert_equal(empty, std::iter::once(Vec::new()))
     }
 
+    fn product_rank_unrank(lengths: Vec<u8>, rank: usize) -> bool {
+        let lengths = lengths.into_iter().map(usize::from).collect_vec();
+        match itertools::product_unrank(rank, &lengths) {
+            Some(positions) => itertools::product_rank(&positions, &lengths) == Some(rank),
+            None => {
+                // The rank is out of the product, which is then small enough to be counted.
+                lengths.contains(&0)
+                    || lengths
+                        .iter()
+                        .try_fold(1_usize, |count, &len| count.checked_mul(len))
+                        .map_or(false, |count| rank >= count)
+            }
+        }
+    }
+
+    fn product_rank_is_position(lengths: Vec<u8>) -> () {
+        let lengths = lengths.into_iter().take(4).map(|len| usize::from(len % 5)).collect_vec();
+        let mut count = 0;
+        for (rank, positions) in lengths.iter().map(|&len| 0..len).multi_cartesian_product().enumerate() {
+            assert_eq!(itertools::product_rank(&positions, &lengths), Some(rank));
+            assert_eq!(itertools::product_unrank(rank, &lengths), Some(positions));
+            count += 1;
+        }
+        assert_eq!(itertools::product_unrank(count, &lengths), None);
+    }
+
+    fn multi_product_extreme_tuples(a: ShiftRange) -> bool {
+        let it = a.multi_cartesian_product();
+        it.min_tuple() == it.clone().min() && it.max_tuple() == it.max()
+    }
+
     fn size_multipeek(a: Iter<u16, Exact>, s: u8) -> bool {
         let mut it = multipeek(a);
         // peek a few times
@@ -950,6 +981,21 @@ quickcheck! {
         let it = a.iter().tuple_combinations::<(_, _, _)>();
         exact_size_for_this(it.clone()) && it.count() == binomial(a.len(), 3)
     }
+
+    fn combinations_get_by_rank(a: Vec<u8>, k: u8, skip: u8, rank: u8) -> TestResult {
+        if k > 4 || a.len() > 10 {
+            return TestResult::discard();
+        }
+        let mut it = a.iter().combinations(k as usize);
+        for _ in 0..skip % 8 {
+            it.next();
+        }
+        let expected = it.clone().nth(rank as usize);
+        let before = it.clone().collect_vec();
+        TestResult::from_bool(
+            it.get_by_rank(rank as usize) == expected && it.collect_vec() == before
+        )
+    }
 }
 
 fn binomial(n: usize, k: usize) -> usize {
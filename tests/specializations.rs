@@ -274,6 +274,120 @@ quickcheck! {
         TestResult::passed()
     }
 
+    fn combinations_set(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        let it = a.iter().combinations_set(n as usize);
+        test_specializations(&it);
+        itertools::assert_equal(
+            it,
+            a.iter().combinations(n as usize).map(|c| c.into_iter().collect()),
+        );
+        let it = a.iter().combinations_hash_set(n as usize);
+        itertools::assert_equal(
+            it,
+            a.iter().combinations(n as usize).map(|c| c.into_iter().collect()),
+        );
+        TestResult::passed()
+    }
+
+    fn combinations_with_complement(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        let it = a.iter().combinations_with_complement(n as usize);
+        test_specializations(&it);
+        itertools::assert_equal(it.clone().map(|(c, _)| c), a.iter().combinations(n as usize));
+        for (chosen, complement) in it {
+            assert_eq!(chosen.len() + complement.len(), a.len());
+            let mut all = chosen.into_iter().chain(complement).collect_vec();
+            all.sort();
+            assert_eq!(all, a.iter().sorted().collect_vec());
+        }
+        TestResult::passed()
+    }
+
+    fn combinations_with_prev(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        let it = a.iter().combinations_with_prev(n as usize);
+        test_specializations(&it);
+        itertools::assert_equal(it.clone().map(|(_, c)| c), a.iter().combinations(n as usize));
+        let v = it.collect_vec();
+        assert!(v.first().map_or(true, |(prev, _)| prev.is_none()));
+        for w in v.windows(2) {
+            assert_eq!(w[1].0.as_ref(), Some(&w[0].1));
+        }
+        TestResult::passed()
+    }
+
+    fn combinations_colex(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        let it = a.iter().combinations_colex(n as usize);
+        test_specializations(&it);
+        let mut colex = a.iter().combinations(n as usize).collect_vec();
+        colex.sort_by_key(|c| c.iter().map(|x| *x as *const u8).rev().collect_vec());
+        itertools::assert_equal(it, colex);
+        TestResult::passed()
+    }
+
+    fn combinations_stride(a: Vec<u8>, n: u8, stride: u8) -> TestResult {
+        if n > 3 || a.len() > 8 || stride == 0 {
+            return TestResult::discard();
+        }
+        let it = a.iter().combinations_stride(n as usize, stride as usize);
+        test_specializations(&it);
+        itertools::assert_equal(it, a.iter().combinations(n as usize).step_by(stride as usize));
+        TestResult::passed()
+    }
+
+    fn combinations_hash(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        test_specializations(&a.iter().combinations_hash(n as usize));
+        TestResult::passed()
+    }
+
+    fn combinations_hash_with(a: Vec<u8>, n: u8) -> TestResult {
+        use std::hash::{BuildHasher, Hash, Hasher};
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        let build_hasher = std::collections::hash_map::RandomState::new();
+        let it = a.iter().combinations_hash_with(n as usize, build_hasher.clone());
+        test_specializations(&it);
+        itertools::assert_equal(it, a.iter().combinations(n as usize).map(|comb| {
+            let mut hasher = build_hasher.build_hasher();
+            hasher.write_usize(n as usize);
+            comb.iter().for_each(|x| x.hash(&mut hasher));
+            hasher.finish()
+        }));
+        TestResult::passed()
+    }
+
+    fn combinations_copied(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        let it = a.iter().combinations_copied(n as usize);
+        test_specializations(&it);
+        itertools::assert_equal(it, a.iter().combinations(n as usize));
+        TestResult::passed()
+    }
+
+    fn combinations_by_key(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 8 {
+            return TestResult::discard();
+        }
+        test_specializations(&a.iter().combinations_by_key(n as usize, |x| *x % 5));
+        TestResult::passed()
+    }
+
     fn combinations_with_replacement(a: Vec<u8>, n: u8) -> TestResult {
         if n > 3 || a.len() > 7 {
             return TestResult::discard();
@@ -282,6 +396,19 @@ quickcheck! {
         TestResult::passed()
     }
 
+    fn combinations_with_replacement_with_prev(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 7 {
+            return TestResult::discard();
+        }
+        let it = a.iter().combinations_with_replacement_with_prev(n as usize);
+        test_specializations(&it);
+        itertools::assert_equal(
+            it.map(|(_, c)| c),
+            a.iter().combinations_with_replacement(n as usize),
+        );
+        TestResult::passed()
+    }
+
     fn permutations(a: Vec<u8>, n: u8) -> TestResult {
         if n > 3 || a.len() > 8 {
             return TestResult::discard();
@@ -294,7 +421,29 @@ quickcheck! {
         if a.len() > 6 {
             return TestResult::discard();
         }
-        test_specializations(&a.iter().powerset());
+        let it = a.iter().powerset();
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
+        TestResult::passed()
+    }
+
+    fn powerset_bounded(a: Vec<u8>, max_size: u8) -> TestResult {
+        if a.len() > 6 {
+            return TestResult::discard();
+        }
+        let it = a.iter().powerset_bounded(max_size as usize % 8);
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
+        TestResult::passed()
+    }
+
+    fn powerset_nonempty(a: Vec<u8>) -> TestResult {
+        if a.len() > 6 {
+            return TestResult::discard();
+        }
+        let it = a.iter().powerset_nonempty();
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
         TestResult::passed()
     }
 
@@ -344,6 +493,17 @@ quickcheck! {
     }
 }
 
+#[test]
+fn combinations_with_replacement_corner_cases() {
+    // Quickcheck rarely generates the degenerate cases, so check them all explicitly.
+    for n in 0..5 {
+        for k in 0..7 {
+            test_specializations(&(0..n).combinations_with_replacement(k));
+            test_specializations(&(0..n).combinations_with_replacement_with_prev(k));
+        }
+    }
+}
+
 quickcheck! {
     fn put_back_qc(test_vec: Vec<i32>) -> () {
         test_specializations(&itertools::put_back(test_vec.iter()));
@@ -1045,6 +1045,547 @@ fn combinations_zero() {
     it::assert_equal((0..0).combinations(0), vec![vec![]]);
 }
 
+#[test]
+fn combinations_map_source_once() {
+    // Elements are mapped once as they enter the pool, not once per combination.
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let calls = std::cell::Cell::new(0);
+            let it = (0..n).map(|x| {
+                calls.set(calls.get() + 1);
+                x * 10
+            });
+            let count = it.combinations(k).collect_vec().len();
+            assert_eq!(count, binomial(n, k));
+            // The only combination of size zero does not need any element.
+            assert_eq!(calls.get(), if k == 0 { 0 } else { n });
+        }
+    }
+}
+
+#[test]
+fn combinations_map_while_after_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut it = (0..4).combinations_map_while(2, |c| {
+        if c == [0, 2] {
+            panic!("closure panicked");
+        }
+        Some(c.to_vec())
+    });
+    assert_eq!(it.next(), Some(vec![0, 1]));
+    assert!(catch_unwind(AssertUnwindSafe(|| it.next())).is_err());
+    assert_eq!(it.next(), Some(vec![0, 3]));
+}
+
+#[test]
+fn combinations_by_key() {
+    let data = vec![(3, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+    let mut sorted = data.clone();
+    sorted.sort_by_key(|x| x.0);
+    for k in 0..=5 {
+        let it = data.iter().combinations_by_key(k, |x| x.0);
+        assert_eq!(it.size_hint(), (binomial(4, k), Some(binomial(4, k))));
+        assert_eq!(it.clone().count(), binomial(4, k));
+        it::assert_equal(it, sorted.iter().combinations(k));
+    }
+}
+
+#[test]
+fn combinations_from_pool() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let it = it::Combinations::from_pool((0..n).collect(), k);
+            assert_eq!(it.n(), n);
+            assert_eq!(it.size_hint(), (binomial(n, k), Some(binomial(n, k))));
+            it::assert_equal(it, (0..n).combinations(k));
+        }
+        let it = it::Powerset::from_pool((0..n).collect());
+        assert_eq!(it.size_hint(), (1 << n, Some(1 << n)));
+        it::assert_equal(it, (0..n).powerset());
+        for k in 0..=4 {
+            let it = it::CombinationsWithReplacement::from_pool((0..n).collect(), k);
+            let expected = (0..n).combinations_with_replacement(k).collect_vec();
+            assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+            assert_eq!(it.clone().count(), expected.len());
+            assert_eq!(it.clone().nth(1).as_ref(), expected.get(1));
+            it::assert_equal(it, expected);
+        }
+    }
+}
+
+#[test]
+fn combinations_into_matrix() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let (matrix, width) = (0..n).combinations_into_matrix(k);
+            assert_eq!(width, k);
+            assert_eq!(matrix.len(), binomial(n, k) * k);
+            let expected = (0..n).combinations(k).flatten().collect_vec();
+            assert_eq!(matrix, expected);
+        }
+    }
+}
+
+#[test]
+fn combinations_max_by_key() {
+    let xs = [3, 1, 4, 1, 5, 9, 2, 6];
+    for n in 0..=xs.len() {
+        for k in 0..=n + 1 {
+            let key = |c: &[&i32]| c.iter().map(|x| **x % 5).sum::<i32>();
+            let expected = xs[..n].iter().combinations(k).max_by_key(|c| key(c));
+            assert_eq!(xs[..n].iter().combinations_max_by_key(k, key), expected);
+        }
+    }
+}
+
+#[test]
+fn combinations_dedup_by() {
+    // Ignore the last coordinate.
+    let same = |a: &[u8], b: &[u8]| a[..a.len() - 1] == b[..b.len() - 1];
+    for n in 0..=6 {
+        for k in 1..=7 {
+            let it = (0..n).combinations_dedup_by(k, same);
+            let expected = (0..n).combinations(k).dedup_by(|a, b| same(a, b));
+            it::assert_equal(it, expected);
+        }
+    }
+    // Only adjacent combinations are removed.
+    let data = [1, 2, 1, 2];
+    let it = data.iter().combinations_dedup_by(1, |a, b| a == b);
+    itertools::assert_equal(it, vec![vec![&1], vec![&2], vec![&1], vec![&2]]);
+    let it = data.iter().combinations_dedup_by(2, |a, b| a[0] == b[0]);
+    itertools::assert_equal(it, vec![vec![&1, &2], vec![&2, &1], vec![&1, &2]]);
+}
+
+#[test]
+fn combinations_partition_point_rank() {
+    for n in 0..=7 {
+        for k in 0..=8 {
+            let count = binomial(n, k);
+            for threshold in 0..=count {
+                for skip in 0..=threshold {
+                    let mut it = (0..n).combinations(k);
+                    let all = it.clone().collect_vec();
+                    if skip > 0 {
+                        it.nth(skip - 1);
+                    }
+                    // Monotone: true for the combinations before `threshold`.
+                    let pred = |c: &[usize]| all[..threshold].iter().any(|x| x == c);
+                    let linear = all[skip..].iter().take_while(|c| pred(c)).count();
+                    assert_eq!(it.partition_point_rank(pred), linear);
+                    // The iterator is not advanced.
+                    it::assert_equal(it, all[skip..].iter().cloned());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn combinations_flagged() {
+    for n in 0..6 {
+        for k in 0..=n + 1 {
+            let flagged = (0..n).combinations_flagged(k).collect_vec();
+            it::assert_equal(
+                flagged.iter().map(|(c, _)| c),
+                &(0..n).combinations(k).collect_vec(),
+            );
+            let flags = flagged.iter().map(|&(_, last)| last).collect_vec();
+            match flags.split_last() {
+                Some((&last, others)) => {
+                    assert!(last);
+                    assert!(!others.contains(&true));
+                    assert_eq!(flagged.last().unwrap().0, (n - k..n).collect_vec());
+                }
+                None => assert!(k > n),
+            }
+        }
+    }
+    // `k == n`: the only combination is the last one.
+    it::assert_equal((0..3).combinations_flagged(3), vec![(vec![0, 1, 2], true)]);
+    // Over an infinite source, no combination is the last one.
+    let mut it = (0..).combinations_flagged(2);
+    assert_eq!(it.nth(5), Some((vec![0, 6], false)));
+}
+
+#[test]
+fn combinations_fold_indices() {
+    for n in 0..6 {
+        for k in 0..=n + 1 {
+            let v = (0..n).map(|i| i * 10).collect_vec();
+            let mut calls = 0;
+            let sum = v
+                .iter()
+                .combinations(k)
+                .fold_indices(0, |acc, indices, items| {
+                    // The positions of the elements in the source are the elements of
+                    // the matching combination of positions.
+                    let expected = (0..n).combinations(k).nth(calls).unwrap();
+                    assert_eq!(indices, &expected[..]);
+                    it::assert_equal(items.iter().copied(), indices.iter().map(|&i| &v[i]));
+                    calls += 1;
+                    acc + items.iter().copied().sum::<usize>()
+                });
+            let mut expected = 0;
+            for c in v.iter().combinations(k) {
+                expected += c.into_iter().sum::<usize>();
+            }
+            assert_eq!(calls, binomial(n, k));
+            assert_eq!(sum, expected);
+        }
+    }
+    // After `next` and `peek`, only the remaining combinations are folded.
+    let mut it = (0..4).combinations(2);
+    it.next();
+    it.peek();
+    let indices = it.fold_indices(Vec::new(), |mut acc, indices, _| {
+        acc.push(indices.to_vec());
+        acc
+    });
+    assert_eq!(indices, (0..4).combinations(2).skip(1).collect_vec());
+}
+
+#[test]
+fn combinations_group_by_first() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let groups = (0..n).combinations(k).group_by_first().collect_vec();
+            for (first, group) in &groups {
+                let size = if k == 0 {
+                    1
+                } else {
+                    binomial(n - 1 - first, k - 1)
+                };
+                assert_eq!(group.clone().count(), size);
+                assert!(group
+                    .clone()
+                    .all(|c| c.first().map_or(true, |x| x == first)));
+            }
+            let firsts = groups.iter().map(|(first, _)| *first);
+            it::assert_equal(firsts, 0..groups.len());
+            let concatenated = groups.into_iter().flat_map(|(_, group)| group);
+            it::assert_equal(concatenated, (0..n).combinations(k));
+        }
+    }
+    // A partially consumed group is completed.
+    let mut it = (0..5).combinations(2);
+    it.nth(1);
+    let mut groups = it.group_by_first();
+    let (first, group) = groups.next().unwrap();
+    assert_eq!(first, 0);
+    it::assert_equal(group, vec![vec![0, 3], vec![0, 4]]);
+    // The groups are lazy, even over an infinite source.
+    let mut groups = (0..).combinations(3).group_by_first();
+    let (first, group) = groups.next().unwrap();
+    assert_eq!(first, 0);
+    it::assert_equal(
+        group.take(3),
+        vec![vec![0, 1, 2], vec![0, 1, 3], vec![0, 1, 4]],
+    );
+    let (first, mut group) = groups.next().unwrap();
+    assert_eq!(first, 1);
+    assert_eq!(group.next(), Some(vec![1, 2, 3]));
+}
+
+#[test]
+fn combinations_single() {
+    for n in 0..=5 {
+        for k in 0..=6 {
+            let count = binomial(n, k);
+            match (0..n).combinations(k).single() {
+                Ok(comb) => {
+                    assert_eq!(count, 1);
+                    assert_eq!(comb, (0..k).collect_vec());
+                }
+                Err(err) => {
+                    assert_ne!(count, 1);
+                    it::assert_equal(err, (0..n).combinations(k));
+                }
+            }
+        }
+    }
+    // Only the remaining combinations are considered.
+    let mut it = (0..4).combinations(2);
+    it.nth(4);
+    assert_eq!(it.single().unwrap(), vec![2, 3]);
+}
+
+#[test]
+fn combinations_on_pool_grow() {
+    use std::cell::Cell;
+
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let pulled = Cell::new(0);
+            let mut reported = Vec::new();
+            let src = (0..n).inspect(|_| pulled.set(pulled.get() + 1));
+            let mut it = src.combinations(k).on_pool_grow(|len| reported.push(len));
+            let mut count = 0;
+            while it.next().is_some() {
+                count += 1;
+            }
+            assert_eq!(count, binomial(n, k));
+            // Exactly once per buffered element, in order.
+            assert_eq!(reported, (1..=pulled.get()).collect_vec());
+        }
+    }
+    // Elements already buffered are not reported.
+    let mut combs = (0..5).combinations(3);
+    combs.next();
+    let mut reported = Vec::new();
+    let rest = combs.on_pool_grow(|len| reported.push(len)).nth(2);
+    assert_eq!(rest, Some(vec![0, 2, 3]));
+    assert_eq!(reported, vec![4, 5]);
+}
+
+#[test]
+fn combinations_next_into() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let mut it = (0..n).combinations(k);
+            let mut buf = Vec::with_capacity(k);
+            let mut all = Vec::new();
+            while it.next_into(&mut buf) {
+                all.push(buf.clone());
+            }
+            assert!(buf.is_empty());
+            assert!(!it.next_into(&mut buf));
+            assert_eq!(all, (0..n).combinations(k).collect_vec());
+        }
+    }
+}
+
+#[test]
+fn combinations_peek() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let expected = (0..n).combinations(k).collect_vec();
+            let mut it = (0..n).combinations(k);
+            for (i, comb) in expected.iter().enumerate() {
+                let remaining = expected.len() - i;
+                assert_eq!(it.peek(), Some(&comb[..]));
+                assert_eq!(it.peek(), Some(&comb[..]));
+                assert_eq!(it.size_hint(), (remaining, Some(remaining)));
+                assert_eq!(it.clone().count(), remaining);
+                for m in 0..3 {
+                    assert_eq!(it.clone().nth(m).as_ref(), expected.get(i + m));
+                }
+                assert_eq!(it.next().as_ref(), Some(comb));
+            }
+            assert_eq!(it.peek(), None);
+            assert_eq!(it.next(), None);
+        }
+    }
+}
+
+#[test]
+fn combinations_index_plan() {
+    for n in 0..7 {
+        for k in 0..9 {
+            let plan = itertools::index_plan(k, n);
+            assert_eq!(plan.len(), binomial(n, k));
+            it::assert_equal(plan, (0..n).combinations(k));
+        }
+    }
+}
+
+#[test]
+fn combinations_colex() {
+    it::assert_equal(
+        (0..5).combinations_colex(3),
+        vec![
+            vec![0, 1, 2],
+            vec![0, 1, 3],
+            vec![0, 2, 3],
+            vec![1, 2, 3],
+            vec![0, 1, 4],
+            vec![0, 2, 4],
+            vec![1, 2, 4],
+            vec![0, 3, 4],
+            vec![1, 3, 4],
+            vec![2, 3, 4],
+        ],
+    );
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let expected = (0..n).combinations_colex(k).collect_vec();
+            for i in 0..=expected.len() + 1 {
+                let mut it = (0..n).combinations_colex(k);
+                assert_eq!(it.nth(i).as_ref(), expected.get(i));
+                let mut pos = i + 1;
+                for m in 0..3 {
+                    let remaining = expected.len().saturating_sub(pos);
+                    assert_eq!(it.size_hint(), (remaining, Some(remaining)));
+                    assert_eq!(it.clone().count(), remaining);
+                    assert_eq!(it.nth(m).as_ref(), expected.get(pos + m));
+                    pos += m + 1;
+                }
+            }
+        }
+    }
+    // Only the elements needed so far are taken from the source.
+    let pulled = std::cell::Cell::new(0);
+    let mut it = (0..)
+        .inspect(|_| pulled.set(pulled.get() + 1))
+        .combinations_colex(2);
+    assert_eq!(it.nth(5), Some(vec![2, 3]));
+    assert_eq!(pulled.get(), 4);
+}
+
+#[test]
+fn combinations_pruned() {
+    let weights = [5, 1, 4, 2, 8, 3, 7, 6, 2, 9];
+    let sum = |indices: &[usize]| indices.iter().map(|&i| weights[i]).sum::<i32>();
+    for n in 0..=weights.len() {
+        for k in 0..=n + 1 {
+            for max in [0, 5, 10, 20, 60] {
+                let mut calls = 0;
+                let it = (0..n).combinations_pruned(k, |indices| {
+                    calls += 1;
+                    sum(indices) <= max
+                });
+                assert_eq!(it.size_hint().1, Some(binomial(n, k)));
+                let pruned = it.collect_vec();
+                let expected = (0..n).combinations(k).filter(|c| sum(c) <= max);
+                assert_eq!(pruned, expected.collect_vec());
+                if max == 0 && k > 0 {
+                    // Each index is tried once as the first element.
+                    assert!(calls <= n);
+                }
+            }
+        }
+    }
+    // The prefixes are never extended past an infeasible one.
+    let mut checked = Vec::new();
+    let it = (0..6).combinations_pruned(3, |indices| {
+        checked.push(indices.to_vec());
+        indices[0] != 1
+    });
+    assert_eq!(it.count(), binomial(6, 3) - binomial(4, 2));
+    assert!(checked.iter().all(|p| p.len() == 1 || p[0] != 1));
+}
+
+#[test]
+fn combinations_stride() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            for stride in 1..=25 {
+                let it = (0..n).combinations_stride(k, stride);
+                let expected = (0..n).combinations(k).step_by(stride).collect_vec();
+                assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+                it::assert_equal(it, expected);
+            }
+        }
+    }
+    // Huge number of combinations, only the ranks fitting in a `usize` are reached.
+    let it = (0..200).combinations_stride(100, usize::MAX / 2);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.count(), 3);
+}
+
+#[test]
+#[should_panic]
+fn combinations_stride_zero() {
+    let _ = (0..5).combinations_stride(2, 0);
+}
+
+#[test]
+fn combinations_restrict_window() {
+    // An iterator with an unknown length.
+    let mut it = (0..10).filter(|_| true).combinations(3);
+    it.next();
+    for start in 0_usize..10 {
+        for end in start.saturating_sub(1)..10 {
+            it.restrict_window(start..=end);
+            let window = (0..10).skip(start).take(end + 1 - start);
+            let expected = window.clone().combinations(3).collect_vec();
+            assert_eq!(it.n(), end + 1 - start);
+            assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+            assert_eq!(it.clone().count(), expected.len());
+            for m in 0..3 {
+                assert_eq!(it.clone().nth(m).as_ref(), expected.get(m));
+                assert_eq!(it.clone().get_by_rank(m).as_ref(), expected.get(m));
+            }
+            it::assert_equal(it.by_ref(), expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn combinations_restrict_window_out_of_range() {
+    let mut it = (0..10).combinations(3);
+    it.restrict_window(5..=10);
+}
+
+#[test]
+fn combinations_poll_new() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let mut it = (0..n).combinations(k);
+            let mut polled = Vec::new();
+            let mut nb_polls = 0;
+            while let Some(new) = it.poll_new() {
+                nb_polls += 1;
+                for comb in new {
+                    assert_eq!(comb.last(), Some(&(nb_polls - 1)));
+                    polled.push(comb);
+                }
+            }
+            assert_eq!(nb_polls, n);
+            assert!(it.poll_new().is_none());
+            let mut expected = (0..n).combinations_colex(k).collect_vec();
+            if k == 0 {
+                expected.clear();
+            }
+            assert_eq!(polled, expected);
+            // The position of the iterator is unchanged.
+            it::assert_equal(it, (0..n).combinations(k));
+        }
+    }
+}
+
+#[test]
+fn combinations_hash() {
+    let data = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+    for k in 0..4 {
+        let combinations = data.iter().combinations(k).collect_vec();
+        let hashes = data.iter().combinations_hash(k).collect_vec();
+        assert_eq!(hashes.len(), combinations.len());
+        let mut seen = std::collections::HashMap::new();
+        for (comb, hash) in combinations.iter().zip(&hashes) {
+            // Equal combinations hash equally, different ones (almost surely) do not.
+            assert_eq!(*seen.entry(hash).or_insert(comb), comb);
+        }
+        for (m, hash) in hashes.iter().enumerate() {
+            assert_eq!(data.iter().combinations_hash(k).nth(m).as_ref(), Some(hash));
+        }
+    }
+    // Combinations of different lengths do not hash the same.
+    assert_ne!(
+        [(), ()].iter().combinations_hash(1).next(),
+        [(), ()].iter().combinations_hash(2).next()
+    );
+}
+
+#[test]
+fn combinations_map_while() {
+    let it = (0..5).combinations_map_while(2, |c| Some(c.to_vec()));
+    it::assert_equal(it, (0..5).combinations(2));
+
+    let pulled = std::cell::Cell::new(0);
+    let mut it = (0..10)
+        .inspect(|_| pulled.set(pulled.get() + 1))
+        .combinations_map_while(2, |c| if c[1] < 3 { Some(c[0] + c[1]) } else { None });
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+    // Only the elements of `[0, 3]` were needed.
+    assert_eq!(pulled.get(), 4);
+}
+
 fn binomial(n: usize, k: usize) -> usize {
     if k > n {
         0
@@ -1188,6 +1729,33 @@ fn combinations_with_replacement() {
     );
 }
 
+#[test]
+fn combinations_with_replacement_with_prev() {
+    for n in 0..5 {
+        for k in 0..5 {
+            let mut it = (0..n).combinations_with_replacement_with_prev(k);
+            let mut expected = (0..n).combinations_with_replacement(k);
+            assert_eq!(it.changed_from(), None);
+            let mut prev = None;
+            while let Some((p, current)) = it.next() {
+                assert_eq!(Some(&current), expected.next().as_ref());
+                assert_eq!(p, prev);
+                let changed_from = it.changed_from().unwrap();
+                if let Some(prev) = &prev {
+                    // Both share a prefix, then the suffix is filled with the same new element.
+                    assert_eq!(prev[..changed_from], current[..changed_from]);
+                    assert_ne!(prev[changed_from], current[changed_from]);
+                    assert!(current[changed_from..].iter().all_equal());
+                } else {
+                    assert_eq!(changed_from, 0);
+                }
+                prev = Some(current);
+            }
+            assert_eq!(expected.next(), None);
+        }
+    }
+}
+
 #[test]
 fn combinations_with_replacement_range_count() {
     for n in 0..=7 {
@@ -1236,6 +1804,13 @@ fn powerset() {
     assert_eq!((0..8).powerset().count(), 1 << 8);
     assert_eq!((0..16).powerset().count(), 1 << 16);
 
+    assert_eq!((0..4).powerset().len(), 16);
+    let mut it = (0..10).powerset();
+    for len in (0..1 << 10).rev() {
+        it.next();
+        assert_eq!(it.len(), len);
+    }
+
     for n in 0..=10 {
         let mut it = (0..n).powerset();
         let len = 2_usize.pow(n);
@@ -1254,6 +1829,833 @@ fn powerset() {
     }
 }
 
+#[test]
+fn combinatorics_send_sync() {
+    use it::structs::*;
+    use std::vec::IntoIter;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    type Src = IntoIter<i32>;
+    assert_send_sync::<Combinations<Src>>();
+    assert_send_sync::<CombinationsWithReplacement<Src>>();
+    assert_send_sync::<CombinationsColex<Src>>();
+    assert_send_sync::<CombinationsMapWhile<Src, fn(&[i32]) -> Option<i32>>>();
+    assert_send_sync::<CombinationsPruned<Src, fn(&[usize]) -> bool>>();
+    assert_send_sync::<Powerset<Src>>();
+    assert_send_sync::<PowersetIndexed<Src>>();
+    assert_send_sync::<MultiProduct<Src>>();
+    assert_send_sync::<MultiProductFill<Src, fn(&[i32], &mut Vec<i32>), i32>>();
+    assert_send_sync::<Permutations<Src>>();
+}
+
+#[test]
+fn powerset_size_hint_tiny_sources() {
+    for n in 0..=2 {
+        let total = 1 << n;
+        // Exact source: the empty set is accounted for from the start.
+        let mut it = (0..n).collect_vec().into_iter().powerset();
+        for remaining in (0..=total).rev() {
+            assert_eq!(it.size_hint(), (remaining, Some(remaining)));
+            assert_eq!(it.clone().count(), remaining);
+            assert_eq!(it.next().is_some(), remaining > 0);
+        }
+        // Inexact source: there is still at least the empty set.
+        let mut it = (0..n).filter(|_| true).powerset();
+        assert_eq!(it.size_hint(), (1, Some(total)));
+        for remaining in (0..=total).rev() {
+            let (low, upp) = it.size_hint();
+            assert!(low <= remaining && upp.map_or(true, |upp| remaining <= upp));
+            assert_eq!(it.next().is_some(), remaining > 0);
+        }
+    }
+}
+
+#[test]
+fn powerset_for_each_layer() {
+    for n in 0..6 {
+        let mut layers = Vec::new();
+        (0..n).powerset().for_each_layer(|k, layer| {
+            let layer = layer.collect_vec();
+            assert!(layer.iter().all(|subset| subset.len() == k));
+            layers.push(layer);
+        });
+        assert_eq!(layers.len(), n + 1);
+        for (k, layer) in layers.iter().enumerate() {
+            assert_eq!(layer.len(), (0..n).combinations(k).count());
+        }
+        it::assert_equal(layers.into_iter().flatten(), (0..n).powerset());
+    }
+
+    // Layers not consumed by the callback are skipped.
+    let mut it = (0..4).powerset();
+    it.nth(2);
+    let mut layers = Vec::new();
+    it.for_each_layer(|k, layer| layers.push((k, layer.next())));
+    assert_eq!(
+        layers,
+        vec![
+            (1, Some(vec![2])),
+            (2, Some(vec![0, 1])),
+            (3, Some(vec![0, 1, 2])),
+            (4, Some(vec![0, 1, 2, 3])),
+        ]
+    );
+}
+
+#[test]
+fn powerset_double_ended_large() {
+    // The total number of subsets overflows a `usize`.
+    let n = usize::BITS as usize;
+    let mut it = (0..n).powerset();
+    assert_eq!(it.next_back(), Some((0..n).collect_vec()));
+    assert_eq!(it.next_back(), Some((1..n).collect_vec()));
+    assert_eq!(
+        it.next_back(),
+        Some((0..n).filter(|&i| i != 1).collect_vec())
+    );
+    assert_eq!(it.next(), Some(vec![]));
+    assert_eq!(it.next(), Some(vec![0]));
+    let mut it = (0..n + 10).powerset_bounded(2);
+    assert_eq!(it.next_back(), Some(vec![n + 8, n + 9]));
+    assert_eq!(it.nth(n + 11), Some(vec![0, 1]));
+}
+
+#[test]
+fn powerset_double_ended() {
+    for n in 0..6 {
+        for nonempty in [false, true] {
+            let new = || {
+                if nonempty {
+                    (0..n).powerset_nonempty()
+                } else {
+                    (0..n).powerset()
+                }
+            };
+            let expected = new().collect_vec();
+            it::assert_equal(new().rev(), expected.iter().rev().cloned());
+            // Alternate directions following the bits of `pattern`.
+            for pattern in 0..64_u32 {
+                let mut it = new();
+                let (mut front, mut back) = (0, expected.len());
+                for step in 0..6 {
+                    let elt = if pattern & (1 << step) == 0 {
+                        it.next()
+                    } else {
+                        it.next_back()
+                    };
+                    if front == back {
+                        assert_eq!(elt, None);
+                    } else if pattern & (1 << step) == 0 {
+                        assert_eq!(elt.as_ref(), Some(&expected[front]));
+                        front += 1;
+                    } else {
+                        back -= 1;
+                        assert_eq!(elt.as_ref(), Some(&expected[back]));
+                    }
+                    let remaining = &expected[front..back];
+                    assert_eq!(it.size_hint(), (remaining.len(), Some(remaining.len())));
+                    assert_eq!(it.clone().count(), remaining.len());
+                    it::assert_equal(it.clone(), remaining.iter().cloned());
+                    let folded = it.clone().fold(Vec::new(), |mut v, x| {
+                        v.push(x);
+                        v
+                    });
+                    assert_eq!(folded, remaining);
+                    for m in 0..remaining.len() + 2 {
+                        assert_eq!(it.clone().nth(m).as_ref(), remaining.get(m));
+                    }
+                    let mut layers = Vec::new();
+                    it.clone().for_each_layer(|_, layer| layers.extend(layer));
+                    assert_eq!(layers, remaining);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn powerset_map_indexed() {
+    for n in 0..6 {
+        let indexed = (0..n)
+            .powerset_map_indexed(|k, rank, subset| (k, rank, subset.to_vec()))
+            .collect_vec();
+        it::assert_equal(indexed.iter().map(|(_, _, s)| s.clone()), (0..n).powerset());
+        for k in 0..=n {
+            let layer = indexed
+                .iter()
+                .filter(|(size, _, _)| *size == k)
+                .collect_vec();
+            it::assert_equal(layer.iter().map(|(_, rank, _)| *rank), 0..binomial(n, k));
+            assert!(layer.iter().all(|(_, _, s)| s.len() == k));
+        }
+    }
+}
+
+#[test]
+fn powerset_count_saturating() {
+    let bits = usize::BITS as usize;
+    // `2^(bits - 1)` subsets fit in a `usize`, `2^bits` do not.
+    assert_eq!((0..bits - 1).powerset().count(), 1 << (bits - 1));
+    assert_eq!((0..bits - 1).powerset().count_saturating(), 1 << (bits - 1));
+    assert_eq!((0..bits).powerset().count_saturating(), usize::MAX);
+    // Without the empty subset, there are `2^bits - 1` subsets left.
+    let mut it = (0..bits).powerset();
+    it.next();
+    assert_eq!(it.clone().count(), usize::MAX);
+    assert_eq!(it.count_saturating(), usize::MAX);
+    // The current layer alone can be too large.
+    let mut it = (0..bits + 8).powerset();
+    it.nth(bits + 9);
+    assert_eq!(it.count_saturating(), usize::MAX);
+    assert_eq!((0..0).powerset().count_saturating(), 1);
+}
+
+#[test]
+#[should_panic = "powerset too large to count"]
+fn powerset_count_overflow() {
+    let bits = usize::BITS as usize;
+    (0..bits).powerset().count();
+}
+
+#[test]
+fn powerset_count_up_to() {
+    for n in 0..=5 {
+        let total: usize = 1 << n;
+        for skip in 0..=total {
+            let mut it = (0..n).powerset();
+            if skip > 0 {
+                it.nth(skip - 1);
+            }
+            for limit in 0..=total + 1 {
+                let expected = (total - skip).min(limit + 1);
+                assert_eq!(it.clone().count_up_to(limit), expected);
+            }
+        }
+        let mut it = (0..n).powerset();
+        it.next_back();
+        assert_eq!(it.count_up_to(usize::MAX), total - 1);
+    }
+    // `2^64` subsets would overflow.
+    assert_eq!((0..64).powerset().count_up_to(usize::MAX - 1), usize::MAX);
+    assert_eq!((0..64).powerset().count_up_to(usize::MAX), usize::MAX);
+    let pulled = std::cell::Cell::new(0);
+    let it = (0..64).inspect(|_| pulled.set(pulled.get() + 1));
+    assert_eq!(it.powerset().count_up_to(100), 101);
+    // 2^7 = 128 > 101 subsets.
+    assert_eq!(pulled.get(), 7);
+}
+
+#[test]
+fn powerset_masked() {
+    let n = 6;
+    for forced_in in (0..n).powerset() {
+        let free = (0..n).filter(|i| !forced_in.contains(i)).collect_vec();
+        for excluded in free.iter().copied().powerset() {
+            let it = (0..n).powerset_masked(&forced_in, &excluded);
+            let expected_len = 1 << (n - forced_in.len() - excluded.len());
+            assert_eq!(it.len(), expected_len);
+            let subsets = it.collect_vec();
+            assert_eq!(subsets.len(), expected_len);
+            for subset in &subsets {
+                assert_eq!(subset[..forced_in.len()], forced_in);
+                assert!(subset.iter().all(|i| !excluded.contains(i)));
+            }
+            let free = free.iter().filter(|i| !excluded.contains(i)).copied();
+            let expected = free
+                .powerset()
+                .map(|s| forced_in.iter().copied().chain(s).collect_vec());
+            it::assert_equal(subsets, expected);
+        }
+    }
+}
+
+#[test]
+fn powerset_masked_lazy() {
+    use std::cell::Cell;
+    let pulled = Cell::new(0);
+    let src = (0..).inspect(|_| pulled.set(pulled.get() + 1));
+    let mut it = src.powerset_masked(&[3, 1], &[2, 0, 5]);
+    assert_eq!(pulled.get(), 0);
+    // The source is taken up to the last forced or excluded element.
+    assert_eq!(it.next(), Some(vec![1, 3]));
+    assert_eq!(pulled.get(), 6);
+    // Then the free elements are taken as they are needed, skipping the excluded ones.
+    assert_eq!(it.next(), Some(vec![1, 3, 4]));
+    assert_eq!(it.next(), Some(vec![1, 3, 6]));
+    assert_eq!(pulled.get(), 7);
+}
+
+#[test]
+#[should_panic = "index 3 is out of range"]
+fn powerset_masked_out_of_range() {
+    // The length of the source is known: no need to iterate.
+    let _ = (0..3).powerset_masked(&[1], &[3]);
+}
+
+#[test]
+fn powerset_masked_unknown_len() {
+    let mut it = (0..4).filter(|_| true).powerset_masked(&[1], &[3]);
+    // The positions are not checked yet.
+    assert_eq!(it.size_hint(), (0, Some(4)));
+    assert_eq!(it.next(), Some(vec![1]));
+    assert_eq!(it.size_hint(), (3, Some(3)));
+}
+
+#[test]
+#[should_panic = "index 3 is out of range"]
+fn powerset_masked_out_of_range_unknown_len() {
+    let _ = (0..3).filter(|_| true).powerset_masked(&[1], &[3]).next();
+}
+
+#[test]
+#[should_panic = "index 4 is out of range"]
+fn powerset_masked_forced_out_of_range() {
+    let _ = (0..3).powerset_masked(&[4], &[]).next();
+}
+
+#[test]
+#[should_panic]
+fn powerset_masked_overlap() {
+    let _ = (0..3).powerset_masked(&[1], &[1]);
+}
+
+#[test]
+fn powerset_bounded() {
+    for n in 0..=6 {
+        for max_size in 0..=n + 1 {
+            let new = || (0..n).powerset_bounded(max_size);
+            let expected = (0..n)
+                .powerset()
+                .filter(|s| s.len() <= max_size)
+                .collect_vec();
+            let len = expected.len();
+            assert_eq!(new().size_hint(), (len, Some(len)));
+            assert_eq!(new().count(), len);
+            it::assert_equal(new(), expected.iter().cloned());
+            it::assert_equal(new().rev(), expected.iter().rev().cloned());
+            assert_eq!(new().fold(0, |acc, _| acc + 1), len);
+            for skip in 0..=len + 1 {
+                let mut it = new();
+                assert_eq!(it.nth(skip).as_ref(), expected.get(skip));
+                it::assert_equal(it, expected.iter().skip(skip + 1).cloned());
+            }
+            let mut layers = Vec::new();
+            new().for_each_layer(|k, layer| layers.push((k, layer.count())));
+            let expected_layers = (0..=max_size.min(n)).map(|k| (k, binomial(n, k)));
+            it::assert_equal(layers, expected_layers);
+        }
+    }
+    // The larger layers are never started, even over an infinite source.
+    it::assert_equal((0..).powerset_bounded(0), vec![vec![]]);
+    let mut it = (0..).powerset_bounded(2);
+    assert_eq!(it.nth(3), Some(vec![2]));
+}
+
+#[test]
+fn powerset_indexed() {
+    let data = ['a', 'b', 'c'];
+    let mut masks = Vec::new();
+    for (mask, subset) in data.iter().powerset_indexed() {
+        let expected = data
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, x)| x)
+            .collect_vec();
+        assert_eq!(subset, expected);
+        masks.push(mask);
+    }
+    assert_eq!(masks, vec![0, 1, 2, 4, 3, 5, 6, 7]);
+
+    let all = (0..10).powerset_indexed().collect_vec();
+    assert_eq!((0..10).powerset_indexed().nth(100), Some(all[100].clone()));
+}
+
+#[test]
+fn powerset_nonempty() {
+    assert_eq!((0..3).powerset_nonempty().next(), Some(vec![0]));
+    for n in 0..=10 {
+        let mut it = (0..n).powerset_nonempty();
+        let len = (1 << n) - 1;
+        assert_eq!(len, it.clone().count());
+        assert_eq!(len, it.len());
+        it::assert_equal(it.clone(), (0..n).powerset().skip(1));
+        for count in (0..len).rev() {
+            assert!(it.next().is_some());
+            assert_eq!(count, it.clone().count());
+            assert_eq!((count, Some(count)), it.size_hint());
+        }
+        assert_eq!(it.next(), None);
+    }
+}
+
+#[test]
+fn powerset_nth_skips_layers() {
+    // Number of subsets of `(0..20)` smaller than the middle layer.
+    let offset: usize = (0..10).map(|k| binomial(20, k)).sum();
+    for rank in [0, 1, 1000, binomial(20, 10) - 1] {
+        let expected = (0..20).combinations(10).nth(rank);
+        assert_eq!((0..20).powerset().nth(offset + rank), expected);
+    }
+    // From an advanced iterator.
+    let mut it = (0..20).powerset();
+    assert_eq!(it.nth(25), Some(vec![0, 5]));
+    assert_eq!(it.nth(offset + 5 - 26), (0..20).combinations(10).nth(5));
+    assert_eq!(it.next(), (0..20).combinations(10).nth(6));
+    assert_eq!(it.size_hint().0, it.clone().count());
+    assert_eq!(it.nth(1 << 20), None);
+    assert_eq!(it.next(), None);
+    // Beyond the end.
+    let mut it = (0..5).powerset();
+    assert_eq!(it.nth(31), Some(vec![0, 1, 2, 3, 4]));
+    assert_eq!(it.next(), None);
+    let mut it = (0..5).powerset();
+    assert_eq!(it.nth(32), None);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+}
+
+#[test]
+#[should_panic]
+fn powerset_len_overflow() {
+    let _ = (0..usize::BITS).powerset().len();
+}
+
+#[test]
+fn multi_product_builder() {
+    let it = it::MultiProductBuilder::new()
+        .push(0..2)
+        .push(vec![4, 5, 6])
+        .build();
+    assert_eq!(it.size_hint(), (6, Some(6)));
+    it::assert_equal(
+        it.clone(),
+        vec![0..2, 4..7].into_iter().multi_cartesian_product(),
+    );
+    let mut it2 = it.clone();
+    it2.next();
+    assert_eq!(it2.size_hint(), (5, Some(5)));
+    assert_eq!(it.count(), 6);
+    assert_eq!(it2.count(), 5);
+
+    let data = [1, 2];
+    let it = it::MultiProductBuilder::new()
+        .push(data.iter().copied())
+        .push(data.iter().map(|x| x * 10).filter(|x| *x > 10))
+        .build();
+    assert_eq!(it.size_hint(), (0, Some(4)));
+    it::assert_equal(it, vec![vec![1, 20], vec![2, 20]]);
+
+    it::assert_equal(it::MultiProductBuilder::<u8>::new().build(), vec![vec![]]);
+}
+
+#[test]
+fn multi_cartesian_product_count_saturates() {
+    let it = vec![0..usize::MAX, 0..3]
+        .into_iter()
+        .multi_cartesian_product();
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+    assert_eq!(it.count(), usize::MAX);
+    let mut it = vec![0..3, 0..usize::MAX]
+        .into_iter()
+        .multi_cartesian_product();
+    it.next();
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+    assert_eq!(it.count(), usize::MAX);
+}
+
+#[test]
+fn multi_cartesian_product_exact_size() {
+    let new = || vec![0..2, 0..3, 0..4].into_iter().multi_cartesian_product();
+    let mut it = new();
+    for remaining in (0..=24).rev() {
+        assert_eq!(it.len(), remaining);
+        it.next();
+    }
+    let zipped = new().zip(0..30);
+    assert_eq!(zipped.len(), 24);
+    it::assert_equal(zipped.map(|(_, i)| i), (0..24).collect::<Vec<_>>());
+    assert_eq!(
+        vec![0..2, 0..0].into_iter().multi_cartesian_product().len(),
+        0
+    );
+    assert_eq!(
+        Vec::<std::ops::Range<i32>>::new()
+            .into_iter()
+            .multi_cartesian_product()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn multi_cartesian_product_rev_order() {
+    use std::collections::HashSet;
+    let axes = [0..2, 10..13, 20..24];
+    let standard = axes.iter().cloned().multi_cartesian_product().collect_vec();
+    let it = axes.iter().cloned().multi_cartesian_product_rev_order();
+    assert_eq!(it.size_hint(), (24, Some(24)));
+    assert_eq!(it.len(), 24);
+    assert_eq!(it.clone().count(), 24);
+    let rev_order = it.collect_vec();
+    // The same products...
+    assert_eq!(
+        standard.iter().collect::<HashSet<_>>(),
+        rev_order.iter().collect::<HashSet<_>>(),
+    );
+    assert_ne!(standard, rev_order);
+    // ...the first axis varying the fastest: that is the standard order of the reversed axes.
+    let reversed = axes.iter().rev().cloned().multi_cartesian_product();
+    it::assert_equal(
+        rev_order,
+        reversed.map(|mut p| {
+            p.reverse();
+            p
+        }),
+    );
+    it::assert_equal(
+        Vec::<std::ops::Range<i32>>::new()
+            .into_iter()
+            .multi_cartesian_product_rev_order(),
+        vec![vec![]],
+    );
+    assert_eq!(
+        vec![0..2, 0..0]
+            .into_iter()
+            .multi_cartesian_product_rev_order()
+            .next(),
+        None
+    );
+}
+
+#[test]
+fn multi_cartesian_product_single_axis() {
+    for n in 0..5 {
+        let expected = (0..n).map(|x| vec![x]).collect_vec();
+        for taken in 0..=n + 1 {
+            let mut it = std::iter::once(0..n).multi_cartesian_product();
+            let mut actual = (&mut it).take(taken).collect_vec();
+            let left = expected.len() - actual.len();
+            assert_eq!(it.size_hint(), (left, Some(left)));
+            assert_eq!(it.clone().count(), left);
+            let last = if left > 0 {
+                expected.last().cloned()
+            } else {
+                None
+            };
+            assert_eq!(it.clone().last(), last);
+            actual.extend(it.by_ref());
+            assert_eq!(actual, expected);
+            assert_eq!(it.next(), None);
+        }
+    }
+}
+
+#[test]
+fn multi_cartesian_product_factories() {
+    use std::cell::Cell;
+
+    for lens in [
+        vec![],
+        vec![0],
+        vec![3],
+        vec![2, 3, 4],
+        vec![2, 0, 3],
+        vec![1, 1, 2],
+    ] {
+        let calls = vec![Cell::new(0); lens.len()];
+        let it = itertools::multi_cartesian_product_factories(lens.iter().zip(&calls).map(
+            |(&len, calls)| {
+                move || {
+                    calls.set(calls.get() + 1);
+                    0..len
+                }
+            },
+        ));
+        let expected = lens.iter().map(|&len| 0..len).multi_cartesian_product();
+        it::assert_equal(it, expected);
+        if let Some(empty) = lens.iter().position(|&len| len == 0) {
+            // The axes to the right of an empty one are never created.
+            for (axis, calls) in calls.iter().enumerate() {
+                assert_eq!(calls.get(), usize::from(axis <= empty));
+            }
+        } else {
+            // An axis restarts once per combination of the values of the axes to its left.
+            let mut product = 1;
+            for (len, calls) in lens.iter().zip(&calls) {
+                assert_eq!(calls.get(), product);
+                product *= len;
+            }
+        }
+    }
+}
+
+#[test]
+fn multi_cartesian_product_weighted() {
+    let weights = [[2.0, 3.0, -1.0], [0.5, 0.0, 4.0]];
+    let axes = weights.map(|w| w.iter().copied().enumerate().collect_vec());
+    let mut it = axes.iter().cloned().multi_cartesian_product_weighted();
+    assert_eq!(it.size_hint(), (9, Some(9)));
+    for i in 0..3 {
+        for j in 0..3 {
+            let (elements, weight) = it.next().unwrap();
+            assert_eq!(elements, vec![i, j]);
+            assert_eq!(weight, weights[0][i] * weights[1][j]);
+        }
+    }
+    assert_eq!(it.next(), None);
+    let mut nullary = Vec::<Vec<((), f64)>>::new()
+        .into_iter()
+        .multi_cartesian_product_weighted();
+    assert_eq!(nullary.next(), Some((vec![], 1.0)));
+    assert_eq!(nullary.next(), None);
+}
+
+#[test]
+fn multi_cartesian_product_extreme_tuples() {
+    for axes in [vec![], vec![vec![]], vec![vec![2, 0, 1], vec![], vec![5]]] {
+        let it = axes.into_iter().multi_cartesian_product();
+        assert_eq!(it.min_tuple(), it.clone().min());
+        assert_eq!(it.max_tuple(), it.max());
+    }
+    let mut it = vec![vec![2, 0, 1], vec![4, 6, 5], vec![8, 7]]
+        .into_iter()
+        .multi_cartesian_product();
+    it.fix_axis(1, 9);
+    assert_eq!(it.min_tuple(), it.clone().min());
+    assert_eq!(it.max_tuple(), it.clone().max());
+    // Already generated products are considered too.
+    it.next();
+    assert_eq!(it.min_tuple(), Some(vec![0, 9, 7]));
+    it.by_ref().count();
+    assert_eq!(it.min_tuple(), None);
+}
+
+#[test]
+fn multi_cartesian_product_display_current() {
+    let mut it = vec!["ab", "c"]
+        .into_iter()
+        .map(|s| s.chars())
+        .multi_cartesian_product();
+    assert!(it.display_current().is_none());
+    for expected in ["(a, c)", "(b, c)"] {
+        it.next();
+        assert_eq!(it.display_current().unwrap().to_string(), expected);
+    }
+    assert_eq!(it.next(), None);
+    assert!(it.display_current().is_none());
+
+    let mut nullary = Vec::<std::ops::Range<i32>>::new()
+        .into_iter()
+        .multi_cartesian_product();
+    nullary.next();
+    assert_eq!(nullary.display_current().unwrap().to_string(), "()");
+}
+
+#[test]
+fn multi_cartesian_product_fix_axis() {
+    let mut it = vec![0..3, 0..4, 0..2].into_iter().multi_cartesian_product();
+    it.fix_axis(0, 9);
+    assert_eq!(it.size_hint(), (8, Some(8)));
+    assert_eq!(it.clone().count(), 8);
+    it.next();
+    assert_eq!(it.size_hint(), (7, Some(7)));
+    assert_eq!(it.clone().count(), 7);
+    assert_eq!(it.clone().last(), Some(vec![9, 3, 1]));
+    it.fix_axis(2, 5);
+    it::assert_equal(it.clone(), (0..4).map(|x| vec![9, x, 5]));
+    it.fix_axis(1, 7);
+    assert_eq!(it.size_hint(), (1, Some(1)));
+    assert_eq!(it.clone().last(), Some(vec![9, 7, 5]));
+    it::assert_equal(it, vec![vec![9, 7, 5]]);
+
+    // A fixed axis can replace an empty one.
+    let mut it = vec![0..2, 0..0].into_iter().multi_cartesian_product();
+    it.fix_axis(1, 3);
+    it::assert_equal(it, vec![vec![0, 3], vec![1, 3]]);
+}
+
+#[test]
+#[should_panic]
+fn multi_cartesian_product_fix_axis_out_of_range() {
+    let mut it = vec![0..3, 0..4].into_iter().multi_cartesian_product();
+    it.fix_axis(2, 0);
+}
+
+#[test]
+fn multi_cartesian_product_fill() {
+    let axes = vec![0..3, 0..4, 0..2];
+    let expected = axes
+        .clone()
+        .into_iter()
+        .multi_cartesian_product()
+        .map(|p| p.iter().rev().map(|x| x * 10).collect_vec())
+        .collect_vec();
+    let mut it = axes
+        .clone()
+        .into_iter()
+        .multi_cartesian_product_fill(|p, buf| buf.extend(p.iter().rev().map(|x| x * 10)));
+    assert_eq!(it.size_hint(), (24, Some(24)));
+    assert_eq!(it.clone().count(), 24);
+    it::assert_equal(it.clone(), expected.clone());
+    it.next();
+    let mut lent = Vec::new();
+    it.for_each_filled(|buf| lent.push(buf.to_vec()));
+    assert_eq!(lent, expected[1..]);
+
+    let nullary = std::iter::empty::<std::ops::Range<i32>>()
+        .multi_cartesian_product_fill(|p, buf| buf.extend_from_slice(p));
+    it::assert_equal(nullary, vec![vec![]]);
+}
+
+#[test]
+fn multi_cartesian_product_find_pruned() {
+    let axes = vec![0..4, 0..5, 0..3, 0..4];
+    for prefix_len in 0..=axes.len() {
+        for modulo in 1..6 {
+            for target in 0..12 {
+                let prefix_pred = |prefix: &[i32]| prefix.iter().sum::<i32>() % modulo != 1;
+                let full_pred = |p: &[i32]| p.iter().sum::<i32>() == target;
+                let product = axes.clone().into_iter().multi_cartesian_product();
+                let expected = product
+                    .clone()
+                    .find(|p| prefix_pred(&p[..prefix_len]) && full_pred(p));
+                let mut nb_prefixes = 0;
+                let mut nb_full = 0;
+                let found = product.find_pruned(
+                    prefix_len,
+                    |prefix| {
+                        nb_prefixes += 1;
+                        prefix_pred(prefix)
+                    },
+                    |p| {
+                        nb_full += 1;
+                        full_pred(p)
+                    },
+                );
+                assert_eq!(found, expected);
+                let nb_naive = axes
+                    .clone()
+                    .into_iter()
+                    .multi_cartesian_product()
+                    .take_while(|p| Some(p) != expected.as_ref())
+                    .filter(|p| prefix_pred(&p[..prefix_len]))
+                    .count()
+                    + usize::from(expected.is_some());
+                assert_eq!(nb_full, nb_naive);
+                assert!(nb_prefixes <= 4 * 5 * 3 * 4);
+            }
+        }
+    }
+
+    let mut it = vec![0..2, 0..2].into_iter().multi_cartesian_product();
+    it.fix_axis(0, 1);
+    assert_eq!(
+        it.find_pruned(1, |p| p[0] == 1, |p| p[1] == 1),
+        Some(vec![1, 1])
+    );
+}
+
+#[test]
+fn multi_cartesian_product_len_if_exact() {
+    for lens in [
+        vec![],
+        vec![0],
+        vec![3],
+        vec![2, 0, 4],
+        vec![2, 3, 4],
+        vec![5; 6],
+    ] {
+        let axes = lens.iter().map(|len| 0..*len).collect_vec();
+        let mut it = axes.into_iter().multi_cartesian_product();
+        let count = it.clone().count();
+        assert_eq!(it.len_if_exact(), Some(count));
+        it.next();
+        assert_eq!(it.len_if_exact(), None);
+    }
+    let mut it = vec![0..3, 0..4].into_iter().multi_cartesian_product();
+    it.fix_axis(1, 2);
+    assert_eq!(it.len_if_exact(), Some(3));
+    let it = vec![0..usize::MAX, 0..2]
+        .into_iter()
+        .multi_cartesian_product();
+    assert_eq!(it.len_if_exact(), None);
+}
+
+#[test]
+fn multi_cartesian_product_debug() {
+    let mut it = (0..2).map(|_| 0..1000).multi_cartesian_product();
+    it.next();
+    it.next();
+    assert_eq!(
+        format!("{:?}", it),
+        "MultiProduct { 0: Some(MultiProductInner { axes: [\
+        AxisSummary { current: Some(0), remaining: (999, Some(999)), len: (1000, Some(1000)) }, \
+        AxisSummary { current: Some(1), remaining: (998, Some(998)), len: (1000, Some(1000)) }\
+        ] }) }"
+    );
+}
+
+#[test]
+fn multi_cartesian_product_empty_clone() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Only its first `clones_left` clones are faithful, then they are empty.
+    struct Flaky {
+        iter: std::ops::Range<i32>,
+        clones_left: Rc<Cell<usize>>,
+    }
+
+    impl Clone for Flaky {
+        fn clone(&self) -> Self {
+            let n = self.clones_left.get();
+            let iter = if n == 0 {
+                0..0
+            } else {
+                self.clones_left.set(n - 1);
+                self.iter.clone()
+            };
+            Self {
+                iter,
+                clones_left: Rc::clone(&self.clones_left),
+            }
+        }
+    }
+
+    impl Iterator for Flaky {
+        type Item = i32;
+        fn next(&mut self) -> Option<i32> {
+            self.iter.next()
+        }
+    }
+
+    let flaky = |iter, clones_left| Flaky {
+        iter,
+        clones_left: Rc::new(Cell::new(clones_left)),
+    };
+
+    // The last axis can be iterated once but is empty when reset.
+    let mut it = vec![flaky(0..2, 1), flaky(2..4, 1)]
+        .into_iter()
+        .multi_cartesian_product();
+    assert_eq!(it.next(), Some(vec![0, 2]));
+    assert_eq!(it.next(), Some(vec![0, 3]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+
+    // The last axis can be reset once only.
+    let mut it = vec![flaky(0..3, 1), flaky(3..5, 2)]
+        .into_iter()
+        .multi_cartesian_product();
+    itertools::assert_equal(
+        it.by_ref(),
+        vec![vec![0, 3], vec![0, 4], vec![1, 3], vec![1, 4]],
+    );
+    assert_eq!(it.next(), None);
+}
+
 #[test]
 fn diff_mismatch() {
     let a = [1, 2, 3, 4];